@@ -0,0 +1,110 @@
+use clap::{Parser, Subcommand, Args, ValueEnum};
+use std::path::PathBuf;
+
+//Command line interface of rusty-swagger
+#[derive(Parser, Debug)]
+#[command(name = "rusty-swagger", version, about = "Generate LaTeX, Markdown or HTML documentation from OpenAPI 3.0 YAML specifications")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command : Command
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Render the documentation for a specification
+    Render(RenderArgs),
+    /// Download a specification from SwaggerHub
+    Fetch(FetchArgs),
+    /// Check a specification for problems which would degrade the documentation
+    Validate(ValidateArgs),
+    /// Manage the mustache templates
+    #[command(subcommand)]
+    Templates(TemplatesCommand)
+}
+
+#[derive(Args, Debug)]
+pub struct RenderArgs {
+    /// Path to the OpenAPI specification
+    #[arg(short, long, value_name = "FILE")]
+    pub spec : PathBuf,
+    /// Where to write the generated document, defaults to documentation.tex, documentation.pdf, documentation.html or the directory documentation for Markdown
+    #[arg(short, long, value_name = "PATH")]
+    pub output : Option<PathBuf>,
+    /// Output format of the documentation
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Latex)]
+    pub format : OutputFormat,
+    /// Directory with templates overriding the built-in ones, see `templates init`
+    #[arg(short, long, value_name = "DIR")]
+    pub template_dir : Option<PathBuf>,
+    /// Project configuration with the company name, address and logo, defaults to rusty-swagger.yaml if it exists
+    #[arg(short, long, value_name = "FILE")]
+    pub config : Option<PathBuf>,
+    /// Typeset the LaTeX document and write the PDF instead
+    #[arg(long)]
+    pub pdf : bool,
+    /// Directory for the LaTeX document, its assets and the intermediate files of the PDF build
+    #[arg(long, value_name = "DIR", default_value = "build")]
+    pub build_dir : PathBuf,
+    /// LaTeX engine used to build the PDF
+    #[arg(long, value_name = "PROGRAM", env = "LATEX_ENGINE", default_value = "xelatex")]
+    pub latex_engine : String,
+    /// Maximal number of LaTeX runs until the cross references settle
+    #[arg(long, value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_runs : u32
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// A single LaTeX document
+    Latex,
+    /// A directory with one Markdown file per tag and per model
+    Markdown,
+    /// A single static HTML page
+    Html
+}
+
+#[derive(Args, Debug)]
+pub struct FetchArgs {
+    /// Owner of the API on SwaggerHub
+    #[arg(long)]
+    pub owner : String,
+    /// Name of the API on SwaggerHub
+    #[arg(long)]
+    pub api : String,
+    /// Version of the API to download
+    //apiVersion is the name of the flag before the subcommands were introduced
+    #[arg(short = 'v', long = "api-version", alias = "apiVersion", value_name = "VERSION")]
+    pub api_version : String,
+    /// API token, needed to access private APIs
+    #[arg(long, env = "SWAGGERHUB_TOKEN", hide_env_values = true, default_value = "")]
+    pub authorization : String,
+    /// Where to write the specification, defaults to swagger-VERSION.yaml
+    #[arg(short, long, value_name = "FILE")]
+    pub output : Option<PathBuf>
+}
+
+#[derive(Args, Debug)]
+pub struct ValidateArgs {
+    /// Path to the OpenAPI specification
+    #[arg(short, long, value_name = "FILE")]
+    pub spec : PathBuf
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplatesCommand {
+    /// Write the default templates into a directory for customisation
+    Init(TemplatesInitArgs)
+}
+
+#[derive(Args, Debug)]
+pub struct TemplatesInitArgs {
+    /// Directory to write the templates to
+    #[arg(short, long, value_name = "DIR", default_value = "templates")]
+    pub template_dir : PathBuf,
+    /// Output format the templates are written for
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Latex)]
+    pub format : OutputFormat,
+    /// Overwrite templates which already exist
+    #[arg(long)]
+    pub force : bool
+}
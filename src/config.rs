@@ -0,0 +1,152 @@
+use yaml_rust::{YamlLoader, Yaml, ScanError};
+use std::fmt;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+//Project settings which are not part of the specification, like the company publishing the documentation
+pub static DEFAULT_CONFIG : &str = "rusty-swagger.yaml";
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, ScanError),
+    Invalid(PathBuf, String)
+}
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "Could not read {}: {}", path.display(), err),
+            ConfigError::Parse(path, err) => write!(f, "Could not parse {}: {}", path.display(), err),
+            ConfigError::Invalid(path, problem) => write!(f, "Invalid configuration {}: {}", path.display(), problem)
+        }
+    }
+}
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub company : String,
+    pub address : Vec<String>,
+    pub website : String,
+    //relative paths are taken relative to the configuration file
+    pub logo : Option<PathBuf>,
+    //introduction used when the specification has no info.description
+    pub introduction : Option<Introduction>,
+    //models returned by all error responses of a status code or range like 4XX
+    pub errors : Vec<(String, String)>
+}
+
+//Markdown files are converted like descriptions, anything else is taken as written in the output format
+#[derive(Debug, PartialEq)]
+pub enum Introduction {
+    Markdown(String),
+    Raw(String)
+}
+impl Config {
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    pub fn load(path : &Path) -> Result<Config, ConfigError> {
+        let content = fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+        let docs = YamlLoader::load_from_str(&content).map_err(|err| ConfigError::Parse(path.to_path_buf(), err))?;
+        let root = match docs.first() {
+            Some(root) => root,
+            None => return Ok(Config::new())
+        };
+        //the address is either a list of lines or a block of text
+        let address = match &root["address"] {
+            Yaml::Array(lines) => lines.iter().filter_map(|line| line.as_str()).map(String::from).collect(),
+            Yaml::String(block) => block.lines().map(String::from).collect(),
+            Yaml::BadValue | Yaml::Null => Vec::new(),
+            _ => return Err(ConfigError::Invalid(path.to_path_buf(), String::from("address must be a list of lines")))
+        };
+        let mut errors = Vec::new();
+        match &root["errors"] {
+            Yaml::Hash(shared_errors) => {
+                for (status, model) in shared_errors {
+                    let status = match status {
+                        Yaml::Integer(code) => code.to_string(),
+                        Yaml::String(code) => code.clone(),
+                        _ => return Err(ConfigError::Invalid(path.to_path_buf(), String::from("errors must be keyed by status codes")))
+                    };
+                    match model.as_str() {
+                        Some(model) => errors.push((status, String::from(model))),
+                        None => return Err(ConfigError::Invalid(path.to_path_buf(), format!("the error model of {} must be the name of a schema", status)))
+                    }
+                }
+            },
+            Yaml::BadValue | Yaml::Null => {},
+            _ => return Err(ConfigError::Invalid(path.to_path_buf(), String::from("errors must map status codes to models")))
+        }
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let logo = root["logo"].as_str().map(|logo| dir.join(logo));
+        let introduction = match root["introduction"].as_str() {
+            Some(file) => {
+                let file = dir.join(file);
+                let content = fs::read_to_string(&file).map_err(|err| ConfigError::Io(file.clone(), err))?;
+                match file.extension().and_then(|extension| extension.to_str()) {
+                    Some("md") | Some("markdown") => Some(Introduction::Markdown(content)),
+                    _ => Some(Introduction::Raw(content))
+                }
+            },
+            None => None
+        };
+        Ok(Config {
+            company : String::from(root["company"].as_str().unwrap_or("")),
+            address,
+            website : String::from(root["website"].as_str().unwrap_or("")),
+            logo,
+            introduction,
+            errors
+        })
+    }
+
+    //the error models must be schemas of the specification, models are the components/schemas or definitions
+    pub fn check_error_models(&self, path : &Path, models : &Yaml) -> Result<(), ConfigError> {
+        for (status, model) in self.errors.iter() {
+            if models[model.as_str()].is_badvalue() {
+                return Err(ConfigError::Invalid(path.to_path_buf(), format!("the error model {} of {} is not a schema of the specification", model, status)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    pub fn test_load_config() {
+        let dir = TempDir::new("config");
+        let path = dir.write(DEFAULT_CONFIG, "company: Example AG\naddress: |\n  Street 1\n  8000 Zurich\nlogo: assets/logo.png\nintroduction: intro.md\nerrors:\n  4XX: ErrorResponse\n  503: Maintenance\n");
+        dir.write("intro.md", "# Welcome\n");
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.company, "Example AG");
+        assert_eq!(config.address, vec!["Street 1", "8000 Zurich"]);
+        assert_eq!(config.website, "");
+        assert_eq!(config.logo, Some(dir.path().join("assets/logo.png")));
+        assert_eq!(config.introduction, Some(Introduction::Markdown(String::from("# Welcome\n"))));
+        assert_eq!(config.errors, vec![(String::from("4XX"), String::from("ErrorResponse")), (String::from("503"), String::from("Maintenance"))]);
+    }
+
+    #[test]
+    pub fn test_check_error_models() {
+        let models = YamlLoader::load_from_str("ErrorResponse: {type: object}\nerrors.problem: {type: object}\n").unwrap().remove(0);
+        let mut config = Config::new();
+        config.errors = vec![(String::from("4XX"), String::from("ErrorResponse")), (String::from("5XX"), String::from("errors.problem"))];
+        assert!(config.check_error_models(Path::new(DEFAULT_CONFIG), &models).is_ok());
+        config.errors.push((String::from("default"), String::from("Missing")));
+        match config.check_error_models(Path::new(DEFAULT_CONFIG), &models) {
+            Err(ConfigError::Invalid(_, problem)) => assert!(problem.contains("Missing")),
+            other => panic!("expected an invalid configuration, got {:?}", other)
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use yaml_rust::Yaml;
+use serde_json::{Map, Number, Value};
+use crate::resolver::Resolver;
+
+//Examples are written as JSON, either as given in the specification or generated from the schema
+
+pub fn to_json(node : &Yaml) -> Value {
+    match node {
+        Yaml::String(val) => Value::String(val.clone()),
+        Yaml::Integer(val) => Value::from(*val),
+        Yaml::Real(val) => val.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number).unwrap_or_else(|| Value::String(val.clone())),
+        Yaml::Boolean(val) => Value::Bool(*val),
+        Yaml::Array(items) => Value::Array(items.iter().map(to_json).collect()),
+        Yaml::Hash(entries) => Value::Object(entries.iter().map(|(key, value)| (key_to_string(key), to_json(value))).collect()),
+        _ => Value::Null
+    }
+}
+
+fn key_to_string(key : &Yaml) -> String {
+    match key {
+        Yaml::String(val) | Yaml::Real(val) => val.clone(),
+        Yaml::Integer(val) => val.to_string(),
+        Yaml::Boolean(val) => val.to_string(),
+        _ => String::from("null")
+    }
+}
+
+//strings are shown as they are, unless they hold JSON for a JSON media type
+pub fn pretty(value : &Value, is_json : bool) -> String {
+    match value {
+        Value::String(text) => {
+            match serde_json::from_str::<Value>(text) {
+                Ok(parsed) if is_json && (parsed.is_object() || parsed.is_array()) => serde_json::to_string_pretty(&parsed).unwrap_or_else(|_| text.clone()),
+                _ => text.clone()
+            }
+        },
+        _ => serde_json::to_string_pretty(value).unwrap_or_default()
+    }
+}
+
+//a single line for the example columns of parameters and fields
+pub fn compact(value : &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        _ => value.to_string()
+    }
+}
+
+pub fn is_json_media_type(media_type : &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or("").trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+//an example following the schema, made of its examples, defaults and first enum values where they are given
+pub fn from_schema<'a>(resolver : &Resolver<'a>, schema : &'a Yaml) -> Value {
+    generate(resolver, schema, &mut Vec::new())
+}
+
+fn generate<'a>(resolver : &Resolver<'a>, schema : &'a Yaml, visited : &mut Vec<&'a str>) -> Value {
+    //recursive models end in null instead of repeating themselves
+    let reference = schema["$ref"].as_str();
+    if let Some(reference) = reference {
+        if visited.contains(&reference) {
+            return Value::Null;
+        }
+        visited.push(reference);
+    }
+    let value = match resolver.resolve(schema) {
+        Ok(schema) => generate_resolved(resolver, schema, visited),
+        Err(_) => Value::Null
+    };
+    if reference.is_some() {
+        visited.pop();
+    }
+    value
+}
+
+fn generate_resolved<'a>(resolver : &Resolver<'a>, schema : &'a Yaml, visited : &mut Vec<&'a str>) -> Value {
+    for key in &["example", "default"] {
+        if !schema[*key].is_badvalue() {
+            return to_json(&schema[*key]);
+        }
+    }
+    if let Some(value) = schema["enum"].as_vec().and_then(|values| values.first()) {
+        return to_json(value);
+    }
+    for composition in &["oneOf", "anyOf"] {
+        if let Some(alternative) = schema[*composition].as_vec().and_then(|alternatives| alternatives.first()) {
+            return generate(resolver, alternative, visited);
+        }
+    }
+    let mut object = Map::new();
+    if let Yaml::Array(ref parts) = schema["allOf"] {
+        for part in parts {
+            match generate(resolver, part, visited) {
+                Value::Object(properties) => object.extend(properties),
+                other if parts.len() == 1 => return other,
+                _ => {}
+            }
+        }
+    }
+    match schema["type"].as_str() {
+        Some("array") => match generate(resolver, &schema["items"], visited) {
+            Value::Null => Value::Array(Vec::new()),
+            item => Value::Array(vec![item])
+        },
+        Some("string") => Value::String(String::from(string_example(schema["format"].as_str().unwrap_or("")))),
+        Some("integer") => Value::from(schema["minimum"].as_i64().unwrap_or(0)),
+        Some("number") => schema["minimum"].as_f64().or_else(|| schema["minimum"].as_i64().map(|minimum| minimum as f64)).and_then(Number::from_f64).map(Value::Number).unwrap_or_else(|| Value::from(0.0)),
+        Some("boolean") => Value::Bool(true),
+        Some("object") | None => {
+            if let Yaml::Hash(ref properties) = schema["properties"] {
+                for (name, property) in properties {
+                    object.insert(key_to_string(name), generate(resolver, property, visited));
+                }
+            }
+            if let Yaml::Hash(_) = schema["additionalProperties"] {
+                object.insert(String::from("key"), generate(resolver, &schema["additionalProperties"], visited));
+            }
+            if object.is_empty() && schema["type"].is_badvalue() && schema["allOf"].is_badvalue() {
+                Value::Null
+            } else {
+                Value::Object(object)
+            }
+        },
+        _ => Value::Null
+    }
+}
+
+fn string_example(format : &str) -> &'static str {
+    match format {
+        "date" => "2024-01-31",
+        "date-time" => "2024-01-31T12:00:00Z",
+        "time" => "12:00:00",
+        "email" => "user@example.com",
+        "uuid" => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "uri" | "url" => "https://example.com",
+        "hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "byte" => "U3dhZ2dlcg==",
+        "binary" => "<binary>",
+        "password" => "********",
+        _ => "string"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    pub fn test_example_from_schema() {
+        let doc = YamlLoader::load_from_str("components:\n  schemas:\n    Node:\n      type: object\n      required: [id]\n      properties:\n        id: {type: string, format: uuid}\n        kind: {type: string, enum: [leaf, inner]}\n        size: {type: integer, default: 3}\n        children: {type: array, items: {$ref: '#/components/schemas/Node'}}\n        meta:\n          allOf:\n            - properties: {created: {type: string, format: date-time}}\n            - properties: {weight: {type: number}}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let schema = YamlLoader::load_from_str("$ref: '#/components/schemas/Node'").unwrap().remove(0);
+        let example = from_schema(&resolver, &schema);
+        assert_eq!(example.to_string(), r#"{"id":"3fa85f64-5717-4562-b3fc-2c963f66afa6","kind":"leaf","size":3,"children":[],"meta":{"created":"2024-01-31T12:00:00Z","weight":0.0}}"#);
+    }
+
+    #[test]
+    pub fn test_pretty_examples() {
+        let example = YamlLoader::load_from_str("{name: Rex, tags: [1, 2.5]}").unwrap().remove(0);
+        assert_eq!(pretty(&to_json(&example), true), "{\n  \"name\": \"Rex\",\n  \"tags\": [\n    1,\n    2.5\n  ]\n}");
+        assert_eq!(pretty(&Value::String(String::from("{\"a\":1}")), true), "{\n  \"a\": 1\n}");
+        assert_eq!(pretty(&Value::String(String::from("a,b")), false), "a,b");
+        assert!(is_json_media_type("application/vnd.foo+json; charset=utf-8"));
+    }
+}
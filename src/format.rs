@@ -0,0 +1,29 @@
+//The output formats differ in how text has to be escaped and how descriptions are converted,
+//the documentation model is built for one of them and rendered with its templates
+pub trait Format {
+    //plain text like names, paths and summaries
+    fn text(&self, text : &str) -> String;
+    //descriptions, which are written in CommonMark
+    fn markdown(&self, markdown : &str) -> String;
+    //descriptions placed in a list item, like the ones of fields
+    fn markdown_item(&self, markdown : &str) -> String {
+        self.markdown(markdown)
+    }
+    fn url(&self, url : &str) -> String;
+}
+
+//labels only contain characters which are safe in \label and \hyperref, file names and html anchors
+pub fn label(name : &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '-' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_label() {
+        assert_eq!(label("Map<string,Foo_Bar>"), "Map-string-Foo_Bar-");
+        assert_eq!(label("Order.shippingAddress"), "Order.shippingAddress");
+    }
+}
@@ -0,0 +1,72 @@
+use pulldown_cmark::{Parser, Options, Event, Tag, html};
+use crate::format::Format;
+
+//A single static html page which can be published without any further tools
+pub struct Html;
+impl Format for Html {
+    fn text(&self, text : &str) -> String {
+        escape_html(text)
+    }
+    //the page is published, so html in descriptions is shown as text and links cannot run scripts
+    fn markdown(&self, markdown : &str) -> String {
+        let events = Parser::new_ext(markdown, Options::ENABLE_TABLES).map(|event| match event {
+            Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+            Event::Start(Tag::Link { link_type, dest_url, title, id }) if !is_safe_url(&dest_url) => Event::Start(Tag::Link { link_type, dest_url : "".into(), title, id }),
+            Event::Start(Tag::Image { link_type, dest_url, title, id }) if !is_safe_url(&dest_url) => Event::Start(Tag::Image { link_type, dest_url : "".into(), title, id }),
+            event => event
+        });
+        let mut converted = String::new();
+        html::push_html(&mut converted, events);
+        converted
+    }
+    fn url(&self, url : &str) -> String {
+        if is_safe_url(url) {
+            escape_html(url)
+        } else {
+            String::new()
+        }
+    }
+}
+
+//relative links and the schemes http, https and mailto
+fn is_safe_url(url : &str) -> bool {
+    let url = url.trim();
+    match url.find([':', '/', '?', '#']) {
+        Some(index) if url[index..].starts_with(':') => {
+            let scheme = url[..index].to_ascii_lowercase();
+            matches!(scheme.as_str(), "http" | "https" | "mailto")
+        },
+        _ => true
+    }
+}
+
+pub fn escape_html(text : &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_html_format() {
+        assert_eq!(Html.text("Map<string,\"Foo\"> & co"), "Map&lt;string,&quot;Foo&quot;&gt; &amp; co");
+        assert_eq!(Html.markdown("**a_b** <b>"), "<p><strong>a_b</strong> &lt;b&gt;</p>\n");
+        assert_eq!(Html.markdown("hi <script>alert(1)</script>"), "<p>hi &lt;script&gt;alert(1)&lt;/script&gt;</p>\n");
+        assert_eq!(Html.markdown("[x](javascript:alert(1)) [y](https://example.com)"), "<p><a href=\"\">x</a> <a href=\"https://example.com\">y</a></p>\n");
+        assert_eq!(Html.url("javascript:alert(1)"), "");
+        assert_eq!(Html.url(" JavaScript:alert(1)"), "");
+        assert_eq!((Html.url("mailto:a@example.com"), Html.url("docs/terms.html")), (String::from("mailto:a@example.com"), String::from("docs/terms.html")));
+    }
+}
@@ -0,0 +1,407 @@
+use regex::Regex;
+use crate::format::Format;
+use pulldown_cmark::{Parser, Options, Event, Tag, TagEnd, Alignment};
+
+//Everything which ends up in the LaTeX templates passes through here
+
+//escape plain text such that it can be placed anywhere in a LaTeX document
+pub fn escape_latex(text : &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '%' | '&' | '#' | '$' | '{' | '}' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            _ => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+//urls are typeset by hyperref, which only needs the characters escaped that would end the argument early
+pub fn escape_url(url : &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '%' | '#' | '{' | '}' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            _ => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+pub struct Latex;
+impl Format for Latex {
+    fn text(&self, text : &str) -> String {
+        escape_latex(text)
+    }
+    fn markdown(&self, markdown : &str) -> String {
+        markdown_to_latex(markdown)
+    }
+    fn url(&self, url : &str) -> String {
+        escape_url(url)
+    }
+}
+
+//markdown as CommonMark describes it, plus tables
+pub fn markdown_to_latex(markdown : &str) -> String {
+    let source = end_lists_at_unindented_lines(markdown);
+    let mut events : Vec<Event> = Parser::new_ext(&source, Options::ENABLE_TABLES).collect();
+    strong_around_emphasis(&mut events);
+    let mut writer = LatexWriter::new();
+    for event in events {
+        writer.event(event);
+    }
+    let mut latex = String::from(writer.latex.trim_end_matches('\n'));
+    if markdown.ends_with('\n') {
+        latex.push('\n');
+    }
+    latex
+}
+
+//descriptions have always been written such that a line which is not indented ends a list,
+//while CommonMark would continue the last item lazily, so a blank line is inserted in between
+fn end_lists_at_unindented_lines(markdown : &str) -> String {
+    let list_item = Regex::new(r"^\s*([-*+]|\d{1,9}[.)])(\s|$)").unwrap();
+    let mut result = String::with_capacity(markdown.len());
+    let mut in_item = false;
+    let mut in_fence = false;
+    for line in markdown.split_inclusive('\n') {
+        let fence = line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~");
+        if in_fence || fence {
+            in_fence = in_fence != fence;
+            in_item = in_item && line.starts_with(char::is_whitespace);
+        } else if line.trim().is_empty() {
+            in_item = false;
+        } else if list_item.is_match(line) {
+            in_item = true;
+        } else if in_item && !line.starts_with(char::is_whitespace) {
+            result.push('\n');
+            in_item = false;
+        }
+        result.push_str(line);
+    }
+    result
+}
+
+//`***text***` is parsed as emphasis around strong, the LaTeX has always been nested the other way round
+fn strong_around_emphasis(events : &mut [Event]) {
+    for i in 0..events.len().saturating_sub(1) {
+        if !matches!((&events[i], &events[i + 1]), (Event::Start(Tag::Emphasis), Event::Start(Tag::Strong))) {
+            continue;
+        }
+        let mut depth = 0;
+        let end = events.iter().skip(i + 1).position(|event| {
+            match event {
+                Event::Start(Tag::Strong) => depth += 1,
+                Event::End(TagEnd::Strong) => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        }).map(|offset| i + 1 + offset);
+        if let Some(end) = end {
+            if let Some(Event::End(TagEnd::Emphasis)) = events.get(end + 1) {
+                events.swap(i, i + 1);
+                events.swap(end, end + 1);
+            }
+        }
+    }
+}
+
+static ENUMERATE_COUNTERS : [&str; 4] = ["enumi", "enumii", "enumiii", "enumiv"];
+
+struct LatexWriter {
+    latex : String,
+    //consecutive paragraphs need a blank line in between, everything else starts on a new line
+    after_paragraph : bool,
+    //whether each open list is numbered
+    lists : Vec<bool>,
+    //raw content of the code block currently being written
+    code : Option<String>,
+    //target of every open link or image, images which are included directly have no link
+    links : Vec<Option<String>>,
+    //the alternative text of included images is not typeset
+    skip_text : bool,
+    in_table_head : bool,
+    cell : usize
+}
+
+impl LatexWriter {
+    fn new() -> LatexWriter {
+        LatexWriter {
+            latex : String::new(),
+            after_paragraph : false,
+            lists : Vec::new(),
+            code : None,
+            links : Vec::new(),
+            skip_text : false,
+            in_table_head : false,
+            cell : 0
+        }
+    }
+
+    fn event(&mut self, event : Event) {
+        match event {
+            Event::Start(tag) => {
+                let after_paragraph = self.after_paragraph;
+                self.after_paragraph = false;
+                self.start(tag, after_paragraph);
+            },
+            Event::End(tag) => {
+                self.after_paragraph = false;
+                self.end(tag);
+            },
+            Event::Text(text) => {
+                if let Some(ref mut code) = self.code {
+                    code.push_str(&text);
+                } else if !self.skip_text {
+                    self.latex.push_str(&escape_latex(&text));
+                }
+            },
+            Event::Code(code) => self.latex.push_str(&format!("\\texttt{{{}}}", escape_latex(&code))),
+            //raw html cannot be rendered, it is shown as it was written
+            Event::Html(html) | Event::InlineHtml(html) => self.latex.push_str(&escape_latex(&html)),
+            Event::SoftBreak => self.latex.push('\n'),
+            //a plain \\ would end the row when the text is placed in a table cell
+            Event::HardBreak => self.latex.push_str("\\newline\n"),
+            Event::Rule => {
+                self.new_line();
+                self.latex.push_str("\\noindent\\rule{\\linewidth}{0.4pt}\n");
+            },
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag : Tag, after_paragraph : bool) {
+        match tag {
+            Tag::Paragraph => {
+                self.new_line();
+                if after_paragraph {
+                    self.latex.push('\n');
+                }
+            },
+            //descriptions live below the generated sections, so headings must not take part in the numbering
+            Tag::Heading { level, .. } => {
+                self.new_line();
+                self.latex.push_str(if (level as usize) <= 2 { "{\\large\\textbf{" } else { "{\\textbf{" });
+            },
+            Tag::BlockQuote(_) => {
+                self.new_line();
+                self.latex.push_str("\\begin{quote}\n");
+            },
+            Tag::CodeBlock(_) => {
+                self.new_line();
+                self.code = Some(String::new());
+            },
+            Tag::HtmlBlock => self.new_line(),
+            Tag::List(None) => {
+                self.new_line();
+                self.latex.push_str("\\begin{itemize}\n");
+                self.lists.push(false);
+            },
+            Tag::List(Some(first)) => {
+                self.new_line();
+                self.latex.push_str("\\begin{enumerate}\n");
+                self.lists.push(true);
+                if first != 1 {
+                    let depth = self.lists.iter().filter(|numbered| **numbered).count();
+                    let counter = ENUMERATE_COUNTERS[depth.min(ENUMERATE_COUNTERS.len()) - 1];
+                    self.latex.push_str(&format!("\\setcounter{{{}}}{{{}}}\n", counter, first.saturating_sub(1)));
+                }
+            },
+            Tag::Item => {
+                self.new_line();
+                self.latex.push_str("\\item ");
+            },
+            Tag::Table(alignments) => {
+                self.new_line();
+                let columns : Vec<&str> = alignments.iter().map(|alignment| match alignment {
+                    Alignment::Center => "c",
+                    Alignment::Right => "r",
+                    _ => "l"
+                }).collect();
+                self.latex.push_str(&format!("\\begin{{tabular}}{{|{}|}}\n\\hline\n", columns.join("|")));
+            },
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.cell = 0;
+            },
+            Tag::TableRow => self.cell = 0,
+            Tag::TableCell => {
+                if self.cell > 0 {
+                    self.latex.push_str(" & ");
+                }
+                if self.in_table_head {
+                    self.latex.push_str("\\ubheader{");
+                }
+                self.cell += 1;
+            },
+            Tag::Emphasis => self.latex.push_str("\\emph{"),
+            Tag::Strong => self.latex.push_str("\\textbf{"),
+            Tag::Link { dest_url, .. } => {
+                self.latex.push_str("\\url[");
+                self.links.push(Some(escape_url(&dest_url)));
+            },
+            //only local images can be included, remote ones are linked instead
+            Tag::Image { dest_url, .. } => {
+                if dest_url.starts_with("http://") || dest_url.starts_with("https://") {
+                    self.latex.push_str("\\url[");
+                    self.links.push(Some(escape_url(&dest_url)));
+                } else {
+                    self.latex.push_str(&format!("\\includegraphics{{{}}}", escape_url(&dest_url)));
+                    self.links.push(None);
+                    self.skip_text = true;
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag : TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.latex.push('\n');
+                self.after_paragraph = true;
+            },
+            TagEnd::Heading(_) => self.latex.push_str("}}\\par\n"),
+            TagEnd::BlockQuote(_) => {
+                self.new_line();
+                self.latex.push_str("\\end{quote}\n");
+            },
+            TagEnd::CodeBlock => {
+                let code = self.code.take().unwrap_or_default();
+                self.latex.push_str("\\begin{flushleft}\\ttfamily\n");
+                let lines : Vec<String> = code.trim_end_matches('\n').lines().map(code_line).collect();
+                self.latex.push_str(&lines.join("\\newline\n"));
+                self.latex.push_str("\n\\end{flushleft}\n");
+            },
+            TagEnd::HtmlBlock | TagEnd::Item => self.new_line(),
+            TagEnd::List(_) => {
+                self.new_line();
+                if self.lists.pop().unwrap_or(false) {
+                    self.latex.push_str("\\end{enumerate}\n");
+                } else {
+                    self.latex.push_str("\\end{itemize}\n");
+                }
+            },
+            TagEnd::Table => self.latex.push_str("\\end{tabular}\n"),
+            TagEnd::TableHead | TagEnd::TableRow => {
+                self.in_table_head = false;
+                self.latex.push_str(" \\\\\n\\hline\n");
+            },
+            TagEnd::TableCell if self.in_table_head => self.latex.push('}'),
+            TagEnd::Emphasis | TagEnd::Strong => self.latex.push('}'),
+            TagEnd::Link | TagEnd::Image => {
+                match self.links.pop() {
+                    Some(Some(url)) => self.latex.push_str(&format!("]{{{}}}", url)),
+                    _ => self.skip_text = false
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn new_line(&mut self) {
+        if !self.latex.is_empty() && !self.latex.ends_with('\n') && !self.latex.ends_with("\\item ") {
+            self.latex.push('\n');
+        }
+    }
+}
+
+//code is typeset line by line such that the indentation survives
+fn code_line(line : &str) -> String {
+    let escaped = escape_latex(line).replace(' ', "\\ ");
+    if line.starts_with(' ') {
+        format!("\\mbox{{}}{}", escaped)
+    } else {
+        escaped
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_markdown_list () {
+        let string = "- test\n- item2\n- a b c";
+        assert_eq!(markdown_to_latex(string), "\\begin{itemize}\n\\item test\n\\item item2\n\\item a b c\n\\end{itemize}");
+    }
+    #[test]
+    pub fn test_markdown_modifiers() {
+        let emph = "*emph*";
+        assert_eq!(markdown_to_latex(emph).trim(),"\\emph{emph}");
+        let emph = "_emph_";
+        assert_eq!(markdown_to_latex(emph).trim(),"\\emph{emph}");
+
+        let strong = "**strong**";
+        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{strong}");
+        let strong = "__strong__";
+        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{strong}");
+         let strong = "***strongemph***";
+        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
+        let strong = "___strongemph___";
+        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
+          let strong = "**_strongemph_**";
+        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
+        let strong = "__*strongemph*__";
+        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
+
+        let url = "[test](https://www.google.ch \"Hallo Velo\")";
+        assert_eq!(markdown_to_latex(url).trim(), "\\url[test]{https://www.google.ch}");
+          let url = "[test](https://www.google.ch)";
+        assert_eq!(markdown_to_latex(url).trim(), "\\url[test]{https://www.google.ch}");
+    }
+
+    #[test]
+    pub fn test_line_and_inline() {
+        let all_in_one = "**strong**\n - test1\n- *emph1*\n- [test](https://www.google.ch)\n_emph_";
+        assert_eq!(markdown_to_latex(all_in_one).trim(), "\\textbf{strong}\n\\begin{itemize}\n\\item test1\n\\item \\emph{emph1}\n\\item \\url[test]{https://www.google.ch}\n\\end{itemize}\n\\emph{emph}");
+        let all_in_one = "**strong**\n -test1\n- *emph1*\n- [test](https://www.google.ch)\n_emph_";
+        assert_ne!(markdown_to_latex(all_in_one).trim(), "\\textbf{strong}\n\\begin{itemize}\n\\item test1\n\\item \\emph{emph1}\n\\item \\url[test]{https://www.google.ch}\n\\end{itemize}\n\\emph{emph}");
+
+    }
+    #[test]
+    pub fn no_markdown() {
+        let test = "hallo velo. normaler test\n";
+        assert_eq!(markdown_to_latex(test), test);
+    }
+
+    #[test]
+    pub fn lazy_match() {
+        let test = "*next* **n**";
+        assert_eq!(markdown_to_latex(test).trim(), "\\emph{next} \\textbf{n}");
+    }
+
+    #[test]
+    pub fn test_escaping() {
+        assert_eq!(escape_latex("100% & #1 costs $5 {a} ~ ^ \\ snake_case"), "100\\% \\& \\#1 costs \\$5 \\{a\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{} snake\\_case");
+        assert_eq!(markdown_to_latex("**50%** of {id}").trim(), "\\textbf{50\\%} of \\{id\\}");
+        assert_eq!(markdown_to_latex("a_b").trim(), "a\\_b");
+    }
+
+    #[test]
+    pub fn test_markdown_blocks() {
+        assert_eq!(markdown_to_latex("# Title\nfirst\nline\\\nbreak\n\nsecond"), "{\\large\\textbf{Title}}\\par\nfirst\nline\\newline\nbreak\n\nsecond");
+        assert_eq!(markdown_to_latex("3. three\n4. four\n   - nested"), "\\begin{enumerate}\n\\setcounter{enumi}{2}\n\\item three\n\\item four\n\\begin{itemize}\n\\item nested\n\\end{itemize}\n\\end{enumerate}");
+        assert_eq!(markdown_to_latex("> *quoted*"), "\\begin{quote}\n\\emph{quoted}\n\\end{quote}");
+    }
+
+    #[test]
+    pub fn test_markdown_code() {
+        assert_eq!(markdown_to_latex("use `snake_case_name`"), "use \\texttt{snake\\_case\\_name}");
+        assert_eq!(markdown_to_latex("```json\n{\n  \"a\": \"50%\"\n}\n```"), "\\begin{flushleft}\\ttfamily\n\\{\\newline\n\\mbox{}\\ \\ \"a\":\\ \"50\\%\"\\newline\n\\}\n\\end{flushleft}");
+    }
+
+    #[test]
+    pub fn test_markdown_tables_and_images() {
+        assert_eq!(markdown_to_latex("| Name | Value |\n|:--|--:|\n| a_b | 1 |"), "\\begin{tabular}{|l|r|}\n\\hline\n\\ubheader{Name} & \\ubheader{Value} \\\\\n\\hline\na\\_b & 1 \\\\\n\\hline\n\\end{tabular}");
+        assert_eq!(markdown_to_latex("![logo](images/logo.png) ![remote](https://x.ch/a.png)"), "\\includegraphics{images/logo.png} \\url[remote]{https://x.ch/a.png}");
+    }
+}
@@ -0,0 +1,282 @@
+use yaml_rust::{YamlLoader, Yaml, ScanError};
+use yaml_rust::yaml::Hash;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::fmt;
+use std::error;
+use std::fs;
+use crate::resolver::{Resolver, ResolveError, pointer_segments};
+
+//Loading of specifications which are split over several files.
+//External references are followed and merged into the components of the main document,
+//such that the rest of the generator only ever sees one document with local references.
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, ScanError),
+    Empty(PathBuf),
+    Reference(PathBuf, ResolveError)
+}
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(path, err) => write!(f, "Could not read {}: {}", path.display(), err),
+            LoadError::Parse(path, err) => write!(f, "Could not parse {}: {}", path.display(), err),
+            LoadError::Empty(path) => write!(f, "{} does not contain a YAML document", path.display()),
+            LoadError::Reference(path, err) => write!(f, "{} (in {})", err, path.display())
+        }
+    }
+}
+impl error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+pub struct Loader {
+    documents : HashMap<PathBuf, Yaml>
+}
+
+struct Bundle {
+    main : PathBuf,
+    //external reference (file, pointer) to the local reference it was rewritten to
+    mapping : HashMap<(PathBuf, String), String>,
+    //components which are already defined or reserved in the main document
+    taken : HashSet<(String, String)>,
+    //external nodes which a component of the main document only refers to, they take over its name
+    reexports : HashMap<(PathBuf, String), (String, String)>,
+    imported : Vec<(String, String, Yaml)>
+}
+
+impl Loader {
+    pub fn new() -> Loader {
+        Loader {
+            documents : HashMap::new()
+        }
+    }
+
+    //load the document at path and merge all documents it references into it
+    pub fn load(&mut self, path : &Path) -> Result<Yaml, LoadError> {
+        let main = fs::canonicalize(path).map_err(|err| LoadError::Io(path.to_path_buf(), err))?;
+        let root = self.document(&main)?.clone();
+        let mut taken = HashSet::new();
+        let mut reexports = HashMap::new();
+        if let Yaml::Hash(ref components) = root["components"] {
+            for (kind, entries) in components {
+                if let (Some(kind), Yaml::Hash(entries)) = (kind.as_str(), entries) {
+                    for (name, entry) in entries {
+                        let name = match name.as_str() {
+                            Some(name) => name,
+                            None => continue
+                        };
+                        taken.insert((String::from(kind), String::from(name)));
+                        //e.g. `Error: {$ref: './common.yaml#/components/schemas/Error'}`
+                        if let (Some(reference), Some(1)) = (entry["$ref"].as_str(), entry.as_hash().map(|entry| entry.len())) {
+                            if let Ok(Some(target)) = locate(reference, &main) {
+                                if target.0 != main {
+                                    reexports.insert(target, (String::from(kind), String::from(name)));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let mut bundle = Bundle {
+            main : main.clone(),
+            mapping : HashMap::new(),
+            taken,
+            reexports,
+            imported : Vec::new()
+        };
+        let mut root = self.bundle(root, &main, &mut bundle)?;
+        if let Yaml::Hash(ref mut h) = root {
+            let components = h.entry(Yaml::String(String::from("components"))).or_insert_with(|| Yaml::Hash(Hash::new()));
+            if let Yaml::Hash(ref mut components) = components {
+                for (kind, name, node) in bundle.imported {
+                    let entries = components.entry(Yaml::String(kind)).or_insert_with(|| Yaml::Hash(Hash::new()));
+                    if let Yaml::Hash(ref mut entries) = entries {
+                        entries.insert(Yaml::String(name), node);
+                    }
+                }
+            }
+        }
+        Ok(root)
+    }
+
+    //parsed documents are cached by their canonical path, since the same file is usually referenced many times
+    fn document(&mut self, path : &Path) -> Result<&Yaml, LoadError> {
+        if !self.documents.contains_key(path) {
+            let content = fs::read_to_string(path).map_err(|err| LoadError::Io(path.to_path_buf(), err))?;
+            let mut docs = YamlLoader::load_from_str(&content).map_err(|err| LoadError::Parse(path.to_path_buf(), err))?;
+            if docs.is_empty() {
+                return Err(LoadError::Empty(path.to_path_buf()));
+            }
+            // Multi document support, only the first document is used
+            self.documents.insert(path.to_path_buf(), docs.swap_remove(0));
+        }
+        Ok(&self.documents[path])
+    }
+
+    //rewrite all references within node, which lives in the document at base
+    fn bundle(&mut self, node : Yaml, base : &Path, bundle : &mut Bundle) -> Result<Yaml, LoadError> {
+        match node {
+            Yaml::Hash(h) => {
+                let mut result = Hash::new();
+                for (k, v) in h {
+                    let v = match (k.as_str(), v) {
+                        (Some("$ref"), Yaml::String(reference)) => Yaml::String(self.rewrite(&reference, base, bundle)?),
+                        (_, v) => self.bundle(v, base, bundle)?
+                    };
+                    result.insert(k, v);
+                }
+                Ok(Yaml::Hash(result))
+            },
+            Yaml::Array(a) => {
+                let mut result = Vec::with_capacity(a.len());
+                for v in a {
+                    result.push(self.bundle(v, base, bundle)?);
+                }
+                Ok(Yaml::Array(result))
+            },
+            other => Ok(other)
+        }
+    }
+
+    fn rewrite(&mut self, reference : &str, base : &Path, bundle : &mut Bundle) -> Result<String, LoadError> {
+        let (target_path, pointer) = match locate(reference, base)? {
+            Some(target) => target,
+            None => {
+                eprintln!("Remote reference {} is not supported", reference);
+                return Ok(String::from(reference));
+            }
+        };
+        if target_path == bundle.main {
+            return Ok(format!("#{}", pointer));
+        }
+        let key = (target_path.clone(), pointer.clone());
+        if let Some(local) = bundle.mapping.get(&key) {
+            return Ok(local.clone());
+        }
+        let target = Resolver::new(self.document(&target_path)?)
+            .lookup(&format!("#{}", pointer))
+            .map_err(|err| LoadError::Reference(base.to_path_buf(), err))?
+            .clone();
+
+        //a component which only refers to the node is replaced by it, anything which is not a component of its own is parked in an extension section of the components
+        let (kind, name) = match bundle.reexports.get(&key) {
+            Some(slot) => slot.clone(),
+            None => component_slot(&pointer, &target_path, &target, bundle)
+                .unwrap_or_else(|| (String::from("x-external"), bundle.mapping.len().to_string()))
+        };
+        //register the local name before descending, such that cyclic models end up as local references
+        let local = format!("#/components/{}/{}", kind, name.replace('~', "~0").replace('/', "~1"));
+        bundle.taken.insert((kind.clone(), name.clone()));
+        bundle.mapping.insert(key, local.clone());
+        let node = self.bundle(target, &target_path, bundle)?;
+        bundle.imported.push((kind, name, node));
+        Ok(local)
+    }
+}
+
+//the canonical file and the pointer a reference leads to, remote references are not followed
+fn locate(reference : &str, base : &Path) -> Result<Option<(PathBuf, String)>, LoadError> {
+    let (file, pointer) = match reference.find('#') {
+        Some(index) => (&reference[..index], &reference[index + 1..]),
+        None => (reference, "")
+    };
+    if file.starts_with("http://") || file.starts_with("https://") {
+        return Ok(None);
+    }
+    let target_path = if file.is_empty() {
+        base.to_path_buf()
+    } else {
+        let relative = base.parent().unwrap_or_else(|| Path::new(".")).join(file);
+        fs::canonicalize(&relative).map_err(|err| LoadError::Io(relative, err))?
+    };
+    Ok(Some((target_path, String::from(pointer))))
+}
+
+//decide under which component name an external node is merged into the main document
+fn component_slot(pointer : &str, path : &Path, target : &Yaml, bundle : &Bundle) -> Option<(String, String)> {
+    let segments = pointer_segments(pointer);
+    let (kind, name) = match segments.as_slice() {
+        [components, kind, name] if components == "components" => (kind.clone(), name.clone()),
+        [definitions, name] if definitions == "definitions" => (String::from("schemas"), name.clone()),
+        _ if looks_like_schema(target) => {
+            //e.g. `./models/User.yaml` contains a single model named after the file
+            let name = segments.last().cloned().unwrap_or_else(|| file_stem(path));
+            (String::from("schemas"), name)
+        },
+        _ => return None
+    };
+    if !bundle.taken.contains(&(kind.clone(), name.clone())) {
+        return Some((kind, name));
+    }
+    //the name is already used by a different model, so qualify it with the file it comes from
+    let qualified = format!("{}-{}", name, file_stem(path));
+    let mut candidate = qualified.clone();
+    let mut counter = 1;
+    while bundle.taken.contains(&(kind.clone(), candidate.clone())) {
+        counter += 1;
+        candidate = format!("{}{}", qualified, counter);
+    }
+    Some((kind, candidate))
+}
+
+fn looks_like_schema(node : &Yaml) -> bool {
+    ["type", "properties", "allOf", "oneOf", "anyOf", "enum", "items"].iter().any(|key| !node[*key].is_badvalue())
+}
+
+fn file_stem(path : &Path) -> String {
+    path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("external").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    pub fn test_external_references() {
+        let dir = TempDir::new("loader");
+        let main = dir.write("main.yaml", "paths: {}\ncomponents:\n  schemas:\n    Error:\n      type: string\n    Order:\n      properties:\n        error:\n          $ref: './common.yaml#/components/schemas/Error'\n        user:\n          $ref: 'models/User.yaml'\n");
+        dir.write("common.yaml", "components:\n  schemas:\n    Error:\n      properties:\n        code:\n          $ref: '#/components/schemas/Code'\n    Code:\n      type: integer\n");
+        dir.write("models/User.yaml", "type: object\nproperties:\n  error:\n    $ref: '../common.yaml#/components/schemas/Error'\n");
+
+        let doc = Loader::new().load(&main).unwrap();
+        let schemas = &doc["components"]["schemas"];
+        assert_eq!(schemas["Order"]["properties"]["error"]["$ref"].as_str(), Some("#/components/schemas/Error-common"));
+        assert_eq!(schemas["Order"]["properties"]["user"]["$ref"].as_str(), Some("#/components/schemas/User"));
+        assert_eq!(schemas["User"]["properties"]["error"]["$ref"].as_str(), Some("#/components/schemas/Error-common"));
+        assert_eq!(schemas["Error-common"]["properties"]["code"]["$ref"].as_str(), Some("#/components/schemas/Code"));
+        assert_eq!(schemas["Code"]["type"].as_str(), Some("integer"));
+    }
+
+    #[test]
+    pub fn test_reexported_component() {
+        let dir = TempDir::new("loader-reexport");
+        let main = dir.write("main.yaml", "paths:\n  /order:\n    get:\n      responses:\n        default:\n          description: failure\n          content:\n            application/json:\n              schema:\n                $ref: '#/components/schemas/Error'\ncomponents:\n  schemas:\n    Error:\n      $ref: './common.yaml#/components/schemas/Error'\n    Order:\n      properties:\n        error:\n          $ref: './common.yaml#/components/schemas/Error'\n");
+        dir.write("common.yaml", "components:\n  schemas:\n    Error:\n      properties:\n        code: {type: integer}\n");
+
+        let doc = Loader::new().load(&main).unwrap();
+        let schemas = &doc["components"]["schemas"];
+        assert!(schemas["Error-common"].is_badvalue());
+        assert_eq!(schemas["Error"]["properties"]["code"]["type"].as_str(), Some("integer"));
+        assert_eq!(schemas["Order"]["properties"]["error"]["$ref"].as_str(), Some("#/components/schemas/Error"));
+        assert_eq!(schemas.as_hash().unwrap().len(), 2);
+    }
+
+    #[test]
+    pub fn test_dotted_file_name() {
+        let dir = TempDir::new("loader-dotted");
+        let main = dir.write("main.yaml", "openapi: 3.0.0\ninfo: {title: Users, version: '1'}\npaths:\n  /user:\n    get:\n      responses:\n        '200':\n          description: The user\n          content:\n            application/json:\n              schema:\n                $ref: 'models/user.v1.yaml'\n");
+        dir.write("models/user.v1.yaml", "type: object\nproperties:\n  name: {type: string}\n");
+
+        let doc = Loader::new().load(&main).unwrap();
+        assert!(!doc["components"]["schemas"]["user.v1"].is_badvalue());
+        let templates = crate::templates::Templates::new(&crate::templates::LATEX_TEMPLATES, None);
+        let documentation = crate::Documentation::new(&doc, &crate::latex::Latex, &crate::config::Config::new());
+        assert!(crate::render_string(&documentation, &templates, "documentation.tex.mustache").unwrap().contains("user.v1"));
+    }
+}
@@ -6,95 +6,183 @@ extern crate serde;
 extern crate reqwest;
 extern crate regex;
 
-use yaml_rust::{YamlLoader, Yaml};
-use std::fs;
+mod resolver;
+mod loader;
+mod cli;
+mod templates;
+mod validate;
+mod latex;
+mod format;
+mod markdown;
+mod html;
+mod pdf;
+mod config;
+mod status;
+mod example;
+#[cfg(test)]
+mod testing;
+
+use yaml_rust::Yaml;
 use std::fmt;
 use std::error;
-use serde::{Serialize};
-use regex::RegexSet;
+use latex::Latex;
+use markdown::Markdown;
+use html::Html;
+use format::{Format, label};
+use resolver::{Resolver, schema_name, pointer};
+use loader::Loader;
+use config::{Config, Introduction, DEFAULT_CONFIG};
+use status::HttpStatus;
+use templates::Templates;
+use cli::{Cli, Command, OutputFormat, RenderArgs, FetchArgs, ValidateArgs, TemplatesCommand, TemplatesInitArgs};
+use clap::Parser;
+use std::path::{Path, PathBuf};
 
 #[tokio::main]
 async fn main(){
-    let mut args = std::env::args();
-    let mut owner = String::new();
-    let mut api = String::new();
-    let mut apiVersion = String::new();
-    let mut authorization = String::new();
-    let mut fileName = String::new();
-    args.next();
-    while let Some(arg) = args.next() {
-        match arg.as_str() {
-            "--owner" | "-o"=> {
-                owner = args.next().expect("An owner should be supplied");
-            },
-            "--api" | "-a" => {
-                api = args.next().expect("An api should be given");
-            },
-            "--apiVersion" | "-v" => {
-                apiVersion = args.next().expect("A version string is needed");
-            },
-             "--authorization" | "-s" => {
-                authorization = args.next().expect("Authorization is needed to access private api");
-            },
-             "--file" | "-f" => {
-                fileName = args.next().expect("Filename should be provided");
-            },
-             "--help" | "-h" => {
-                 println!("usage: rusty_swagger --owner [API_OWNER] --api [API_NAME] --apiVersion [API_VERSION] --authorization [API_TOKEN]");
-                 println!("rusty_swagger --file [PATH_TO_SWAGGER_DEFINITION]");
-                std::process::exit(0);
-            },
-            _ => {
-                panic!("We need three arguments");
-            }
-        }
-    }
-    if fileName.is_empty() {
-        if owner.is_empty() || api.is_empty() || apiVersion.is_empty() {
-            println!("We need either e filename or the swaggerhub options. Use the --help switch");
-            std::process::exit(1);
-        }
-        get_swagger_config(&owner, &api, &apiVersion, &authorization).await;
-        fileName = format!("swagger-{}.yaml", apiVersion);
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Render(args) => render(&args),
+        Command::Fetch(args) => fetch(&args).await,
+        Command::Validate(args) => validate(&args),
+        Command::Templates(TemplatesCommand::Init(args)) => init_templates(&args)
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
     }
-    let content = fs::read_to_string(fileName).expect("Could not read swagger yaml");
+}
 
-    let docs = YamlLoader::load_from_str(content.as_str()).unwrap();
+fn load(spec : &Path) -> Result<Yaml, Box<dyn error::Error>> {
+    //external references are merged into the main document, so we only deal with one yaml::Yaml from here on
+    Ok(Loader::new().load(spec)?)
+}
 
-    // Multi document support, doc is a yaml::Yaml
-    let doc = &docs[0];
-    let theDoc = Documentation::new(doc);
-    let template = mustache::compile_path("documentation.tex.mustache").expect("could not find template");
-    let output = match template.render_to_string(&theDoc) {
-        Ok(content) => {
-            content
+fn render(args : &RenderArgs) -> Result<(), Box<dyn error::Error>> {
+    let doc = load(&args.spec)?;
+    let config_path = match args.config {
+        Some(ref path) => Some(path.clone()),
+        None if Path::new(DEFAULT_CONFIG).exists() => Some(PathBuf::from(DEFAULT_CONFIG)),
+        None => None
+    };
+    let mut config = match config_path {
+        Some(ref path) => Config::load(path)?,
+        None => Config::new()
+    };
+    if let Some(ref path) = config_path {
+        let models = if doc["components"]["schemas"].is_badvalue() { &doc["definitions"] } else { &doc["components"]["schemas"] };
+        config.check_error_models(path, models)?;
+    }
+    let templates = Templates::new(templates::defaults(args.format), args.template_dir.clone());
+    if args.pdf && args.format != OutputFormat::Latex {
+        return Err("--pdf is only available for the latex format".into());
+    }
+    match args.format {
+        OutputFormat::Latex if args.pdf => {
+            let output = args.output.clone().unwrap_or_else(|| PathBuf::from("documentation.pdf"));
+            let mut build = pdf::Build::new(args.latex_engine.clone(), args.build_dir.clone(), args.max_runs);
+            //the logo is copied next to the document
+            if let Some(logo) = config.logo.take() {
+                config.logo = logo.file_name().map(PathBuf::from);
+                build.add_file(logo);
+            }
+            let tex = render_string(&Documentation::new(&doc, &Latex, &config), &templates, "documentation.tex.mustache")?;
+            let name = output.file_stem().and_then(|stem| stem.to_str()).unwrap_or("documentation");
+            let pdf = build.run(name, &tex)?;
+            std::fs::copy(&pdf, &output).map_err(|err| format!("could not copy {} to {}: {}", pdf.display(), output.display(), err))?;
+            Ok(())
+        },
+        OutputFormat::Latex => {
+            let output = args.output.clone().unwrap_or_else(|| PathBuf::from("documentation.tex"));
+            render_single(&Documentation::new(&doc, &Latex, &config), &templates, "documentation.tex.mustache", &output)
+        },
+        OutputFormat::Html => {
+            let output = args.output.clone().unwrap_or_else(|| PathBuf::from("documentation.html"));
+            render_single(&Documentation::new(&doc, &Html, &config), &templates, "documentation.html.mustache", &output)
         },
-        Err(_) =>
-        {
-            panic!("could not render");
+        OutputFormat::Markdown => {
+            let output = args.output.clone().unwrap_or_else(|| PathBuf::from("documentation"));
+            markdown::write(&Documentation::new(&doc, &Markdown, &config), &templates, &output)?;
+            Ok(())
         }
-    };
-    std::fs::write("documentation.tex", output).expect("Could not write tex");
-    //start filling our model
-   // println!("{:?}", theDoc);
+    }
+}
+
+//formats which produce a single document from the main template
+fn render_single(the_doc : &Documentation, templates : &Templates, name : &str, output : &Path) -> Result<(), Box<dyn error::Error>> {
+    let rendered = render_string(the_doc, templates, name)?;
+    std::fs::write(output, rendered).map_err(|err| format!("could not write {}: {}", output.display(), err))?;
+    Ok(())
+}
+
+fn render_string(the_doc : &Documentation, templates : &Templates, name : &str) -> Result<String, Box<dyn error::Error>> {
+    let template = templates.compile(name)?;
+    Ok(template.render_to_string(the_doc).map_err(|err| format!("could not render documentation: {}", err))?)
+}
+
+async fn fetch(args : &FetchArgs) -> Result<(), Box<dyn error::Error>> {
+    let output = args.output.clone().unwrap_or_else(|| PathBuf::from(format!("swagger-{}.yaml", args.api_version)));
+    let body = get_swagger_config(&args.owner, &args.api, &args.api_version, &args.authorization).await?;
+    std::fs::write(&output, body).map_err(|err| format!("could not write {}: {}", output.display(), err))?;
+    println!("{}", output.display());
+    Ok(())
+}
+
+fn validate(args : &ValidateArgs) -> Result<(), Box<dyn error::Error>> {
+    let doc = load(&args.spec)?;
+    let problems = validate::validate(&doc);
+    for problem in problems.iter() {
+        println!("{}: {}", args.spec.display(), problem);
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("found {} problem(s) in {}", problems.len(), args.spec.display()).into())
+    }
 }
-pub async fn get_swagger_config(owner : &str, api : &str, version : &str, authorization : &str){
+
+fn init_templates(args : &TemplatesInitArgs) -> Result<(), Box<dyn error::Error>> {
+    for path in templates::init(templates::defaults(args.format), &args.template_dir, args.force)? {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+pub async fn get_swagger_config(owner : &str, api : &str, version : &str, authorization : &str) -> Result<String, Box<dyn error::Error>> {
     let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert("Authorization", authorization.parse().unwrap());
-    headers.insert("Accept", "application/yaml".parse().unwrap());
-    let client = reqwest::Client::builder().default_headers(headers).gzip(true).build().expect("Could not build HttpClient");
-    let body = client.get(&format!("https://api.swaggerhub.com/apis/{}/{}/{}", owner, api, version)).send().await.expect("Request sending failed").text().await.expect("failure retrieving body");
-    println!("{}", version);
-    std::fs::write(&format!("swagger-{}.yaml", version), body).expect("Could not write swagger definition");
+    if !authorization.is_empty() {
+        headers.insert("Authorization", authorization.parse()?);
+    }
+    headers.insert("Accept", "application/yaml".parse()?);
+    let client = reqwest::Client::builder().default_headers(headers).gzip(true).build()?;
+    let response = client.get(&format!("https://api.swaggerhub.com/apis/{}/{}/{}", owner, api, version)).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("SwaggerHub answered with {}", response.status()).into());
+    }
+    Ok(response.text().await?)
 }
 
 //Structures and function to parse and fill the latex templates
 #[derive(Serialize, Debug)]
 struct Documentation {
     title : String,
-    host : String,
-    base_url : String,
-    requests : Vec<Request>,
+    //shown as the author of the document
+    author : String,
+    info : Info,
+    //info.description, or the introduction configured for the project
+    introduction : String,
+    company : Company,
+    servers : Vec<Server>,
+    has_servers : bool,
+    security_schemes : Vec<SecurityScheme>,
+    has_security_schemes : bool,
+    //the security requirements of all operations which do not declare their own
+    security : Vec<SecurityRequirement>,
+    has_security : bool,
+    //error models configured to be returned by all errors of a status
+    errors : Vec<SharedError>,
+    has_errors : bool,
+    tags : Vec<Tag>,
     schemas : Vec<Schema>
 }
 
@@ -114,315 +202,1182 @@ impl error::Error for TypeNotFound {
     }
 }
 
-fn get_typeName(schema : & Yaml) -> Result<String,TypeNotFound>{
-    if let Yaml::String(val) = &schema["schema"]["type"] {
+fn get_type_name(resolver : &Resolver, schema : &Yaml) -> Result<String,TypeNotFound>{
+    if !schema["schema"].is_badvalue() {
+        get_type_name_without_schema(resolver, &schema["schema"])
+    } else if let Yaml::String(val) = &schema["type"] {
+        Ok(val.clone())
+    }
+    else {
+        Err(TypeNotFound {})
+    }
+}
+
+fn shorten_type_name(long_type_name : &str) -> String{
+    //we can shorten the name by spliting the name at dots and rejoin the paths only if the first letter is a capital
+    let class_parts : Vec<&str> = long_type_name.split('.')
+        .filter(|part| part.chars().next().is_some_and(char::is_uppercase))
+        .collect();
+    //names like user.v1 are no java classes and stay as they are
+    if class_parts.is_empty() {
+        return String::from(long_type_name);
+    }
+    class_parts.join(".")
+}
+
+fn get_type_name_without_schema(resolver : &Resolver, prop : &Yaml) -> Result<String,TypeNotFound> {
+    find_type_name(resolver, prop, &mut Vec::new())
+}
+
+fn find_type_name(resolver : &Resolver, prop : &Yaml, visited : &mut Vec<String>) -> Result<String,TypeNotFound> {
+    if let Yaml::String(reference) = &prop["$ref"] {
+        //references to named models are linked by name, everything else is followed to the actual definition
+        if let Some(name) = schema_name(reference) {
+            return Ok(shorten_type_name(&name));
+        }
+        //pointers into a schema may lead back to themselves
+        if visited.contains(reference) {
+            return Err(TypeNotFound {});
+        }
+        let target = resolver.resolve(prop).map_err(|_| TypeNotFound {})?;
+        visited.push(reference.clone());
+        let type_name = find_type_name(resolver, target, visited);
+        visited.pop();
+        return type_name;
+    }
+    //a single allOf entry is the usual way to attach a description to a referenced model
+    if let Yaml::Array(ref parts) = prop["allOf"] {
+        if parts.len() == 1 {
+            return find_type_name(resolver, &parts[0], visited);
+        }
+    }
+    for composition in &["oneOf", "anyOf"] {
+        if let Yaml::Array(ref alternatives) = prop[*composition] {
+            let names : Vec<String> = alternatives.iter().map(|alternative| find_type_name(resolver, alternative, visited).unwrap_or_else(|_| String::from("object"))).collect();
+            return Ok(names.join(" | "));
+        }
+    }
+    if let Yaml::String(val) = &prop["type"] {
         match val.as_str() {
         "array" => {
-            let innerType = if let Yaml::String(innerProp) = &schema["schema"]["items"]["type"]
-            {
-                String::from(innerProp.as_str())
-            } else if let Yaml::String(innerProp) = &schema["schema"]["items"]["$ref"]{
-                let types : Vec<&str> = val.split("/").collect();
-                let theType = types.last().expect("no last element");
-                String::from(shorten_type_name(theType))
-            } else {
-                String::from("unknown property")
-            };
-            Ok( String::from(format!("{}[]", innerType)))
+            let inner_type = find_type_name(resolver, &prop["items"], visited).unwrap_or_else(|_| String::from("unknown property"));
+            Ok(format!("{}[]", inner_type))
         },
         "object" => {
-            //we have an object, since every other case should be handled by a reference to a model
-            let innerType = if let Yaml::String(addProp) = &schema["additionalProperties"]["$ref"] {
-                let types : Vec<&str> = val.split("/").collect();
-                let theType = types.last().expect("no last element");
-                String::from(shorten_type_name(theType))
-            } else if let Yaml::String(addProp) = &schema["additionalProperties"]["type"] {
-                String::from(addProp.as_str())
-            } else {
-                String::from("unknown type")
-            };
-            Ok( String::from(format!("Map<string,{}>", shorten_type_name(&innerType))))
+            //for responses we either have a map, or a field holding the array
+            let inner_type = find_type_name(resolver, &prop["additionalProperties"], visited).unwrap_or_else(|_| String::from("unknown type"));
+            Ok(format!("Map<string,{}>", inner_type))
         },
         _ => {
-             Ok(String::from(val.as_str()))
+            Ok(val.clone())
         }
     }
-    } else if let Yaml::String(val) = &schema["schema"]["$ref"] {
-        let types : Vec<&str> = val.split("/").collect();
-        let theType = types.last().expect("no last element");
-        println!("{}",val);
-       Ok(shorten_type_name(&theType))
-    }else if  let Yaml::String(val) = &schema["type"] {
-        Ok(String::from(val.as_str()))
-    }
-    else {
+    } else {
         Err(TypeNotFound {})
     }
 }
 
-fn shorten_type_name(long_type_name : &str) -> String{
-    //we can shorten the name by spliting the name at dots and rejoin the paths only if the first letter is a capital
-    if ! long_type_name.contains(".") {
-        return String::from(long_type_name);
+//scalar values like enum constants as they are written in the document
+fn yaml_to_string(node : &Yaml) -> String {
+    match node {
+        Yaml::String(val) | Yaml::Real(val) => val.clone(),
+        Yaml::Integer(val) => val.to_string(),
+        Yaml::Boolean(val) => val.to_string(),
+        Yaml::Null => String::from("null"),
+        _ => String::new()
+    }
+}
+
+//the example of a parameter, header or property on a single line
+fn get_example<'a>(resolver : &Resolver<'a>, node : &'a Yaml) -> String {
+    let example = if !node["example"].is_badvalue() {
+        &node["example"]
+    } else if let Some(first) = node["examples"].as_hash().and_then(|examples| examples.values().next()) {
+        match resolver.resolve(first) {
+            Ok(first) => &first["value"],
+            Err(err) => {
+                eprintln!("{}", err);
+                &Yaml::BadValue
+            }
+        }
+    } else {
+        &node["schema"]["example"]
+    };
+    example::compact(&example::to_json(example))
+}
+
+//properties of a model together with the model they were inherited from through allOf
+struct CollectedProperties<'a> {
+    properties : Vec<(String, &'a Yaml, Option<String>)>,
+    required : Vec<String>,
+    parents : Vec<String>
+}
+
+fn collect_properties<'a>(resolver : &Resolver<'a>, model : &'a Yaml, inherited_from : Option<String>, collected : &mut CollectedProperties<'a>, visited : &mut Vec<&'a str>) {
+    if let Yaml::Array(ref required_properties) = model["required"] {
+        collected.required.extend(required_properties.iter().map(yaml_to_string));
+    }
+    if let Yaml::Array(ref parts) = model["allOf"] {
+        for part in parts {
+            let provenance = match &part["$ref"] {
+                Yaml::String(reference) => {
+                    if visited.contains(&reference.as_str()) {
+                        eprintln!("allOf reference {} is part of a cycle", reference);
+                        continue;
+                    }
+                    visited.push(reference.as_str());
+                    match schema_name(reference) {
+                        Some(name) => {
+                            let name = shorten_type_name(&name);
+                            if inherited_from.is_none() {
+                                collected.parents.push(name.clone());
+                            }
+                            Some(name)
+                        },
+                        None => inherited_from.clone()
+                    }
+                },
+                _ => inherited_from.clone()
+            };
+            match resolver.resolve(part) {
+                Ok(part) => collect_properties(resolver, part, provenance, collected, visited),
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+    }
+    if let Yaml::Hash(ref inner) = model["properties"] {
+        for (prop_name, prop_node) in inner {
+            //keys like 404 or yes are no strings in yaml
+            let prop_name = yaml_to_string(prop_name);
+            //a redefinition in a later part of allOf overrides the inherited property
+            collected.properties.retain(|(name, _, _)| *name != prop_name);
+            collected.properties.push((prop_name, prop_node, inherited_from.clone()));
+        }
+    }
+}
+
+//inline object definitions get a synthetic model of their own, named after the path leading to them
+fn get_nested_type_name<'a>(resolver : &Resolver<'a>, format : &dyn Format, name : &str, prop : &'a Yaml, nested : &mut Vec<Schema>) -> Option<String> {
+    if !prop["$ref"].is_badvalue() {
+        return None;
+    }
+    let is_inline_object = if let Yaml::Array(ref parts) = prop["allOf"] {
+        parts.len() > 1
+    } else {
+        !prop["properties"].is_badvalue()
+    };
+    if is_inline_object {
+        //keep the parent before its children in the list of models
+        let index = nested.len();
+        let schema = get_schema(resolver, format, String::from(name), prop, nested);
+        nested.insert(index, schema);
+        return Some(String::from(name));
+    }
+    match prop["type"].as_str() {
+        Some("array") => get_nested_type_name(resolver, format, name, &prop["items"], nested).map(|inner_type| format!("{}[]", inner_type)),
+        Some("object") => get_nested_type_name(resolver, format, name, &prop["additionalProperties"], nested).map(|inner_type| format!("Map<string,{}>", inner_type)),
+        _ => None
+    }
+}
+
+fn get_schema<'a>(resolver : &Resolver<'a>, format : &dyn Format, name : String, model : &'a Yaml, nested : &mut Vec<Schema>) -> Schema {
+    let mut collected = CollectedProperties {
+        properties : Vec::new(),
+        required : Vec::new(),
+        parents : Vec::new()
+    };
+    collect_properties(resolver, model, None, &mut collected, &mut Vec::new());
+    let mut properties = Vec::new();
+    for (prop_name, prop_node, inherited_from) in collected.properties {
+        let field_name = shorten_type_name(&prop_name);
+        //inherited inline objects are documented with the parent, so only their name is needed here
+        let nested_type_name = match inherited_from {
+            Some(ref parent) => get_nested_type_name(resolver, format, &format!("{}.{}", parent, prop_name), prop_node, &mut Vec::new()),
+            None => get_nested_type_name(resolver, format, &format!("{}.{}", name, prop_name), prop_node, nested)
+        };
+        let type_name = match nested_type_name {
+            Some(type_name) => type_name,
+            None => get_type_name_without_schema(resolver, prop_node).unwrap_or_else(|_| String::from("unknown type"))
+        };
+        let is_property_required = collected.required.contains(&prop_name);
+        let mut description = String::from("");
+        if let Yaml::String(val) = &prop_node["description"] {
+            description = format.markdown_item(val.as_str());
+        }
+        let mut the_field = Field::new(format, field_name,type_name,is_property_required,description, get_example(resolver, prop_node));
+        the_field.set_inherited_from(format, inherited_from);
+        properties.push(the_field);
+    }
+    let mut enum_values = Vec::new();
+    if let Yaml::Array(ref inner) = model["enum"] {
+        for enum_name in inner {
+            enum_values.push(Field::new(format, yaml_to_string(enum_name), String::from(""), false, String::from(""), String::from("")));
+        }
+    }
+    let one_of = get_variants(resolver, format, &model["oneOf"]);
+    let any_of = get_variants(resolver, format, &model["anyOf"]);
+    let discriminator = if let Yaml::String(property_name) = &model["discriminator"]["propertyName"] {
+        let mut mapping = Vec::new();
+        if let Yaml::Hash(ref inner) = model["discriminator"]["mapping"] {
+            for (value, reference) in inner {
+                let value = yaml_to_string(value);
+                //mapping values are either references or plain schema names
+                let reference = reference.as_str().unwrap_or("");
+                let type_name = schema_name(reference).unwrap_or_else(|| String::from(reference));
+                mapping.push(Field::new(format, value, shorten_type_name(&type_name), false, String::from(""), String::from("")));
+            }
+        }
+        Some(Discriminator::new(format, property_name.clone(), mapping))
+    } else {
+        None
+    };
+    let composition = if collected.parents.is_empty() && one_of.is_empty() && any_of.is_empty() && discriminator.is_none() {
+        None
+    } else {
+        Some(Composition::new(collected.parents.into_iter().map(|parent| Field::new(format, String::from(""), parent, false, String::from(""), String::from(""))).collect(), one_of, any_of, discriminator))
+    };
+    Schema::new(format, name, properties, enum_values, composition)
+}
+
+//the alternatives of a oneOf or anyOf, each linked to its model
+fn get_variants(resolver : &Resolver, format : &dyn Format, alternatives : &Yaml) -> Vec<Field> {
+    let mut variants = Vec::new();
+    if let Yaml::Array(ref alternatives) = alternatives {
+        for alternative in alternatives {
+            let type_name = get_type_name_without_schema(resolver, alternative).unwrap_or_else(|_| String::from("object"));
+            let description = format.markdown_item(alternative["description"].as_str().unwrap_or(""));
+            variants.push(Field::new(format, String::from(""), type_name, false, description, String::from("")));
+        }
+    }
+    variants
+}
+
+static HTTP_METHODS : [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+//parameters shared by all operations of a path item, overridden by operation parameters with the same name and location
+fn get_parameters<'a>(resolver : &Resolver<'a>, path_item : &'a Yaml, operation : &'a Yaml) -> Vec<&'a Yaml> {
+    let mut parameters : Vec<&'a Yaml> = Vec::new();
+    for parameter_list in &[&path_item["parameters"], &operation["parameters"]] {
+        if let Yaml::Array(ref parameter_list) = parameter_list {
+            for entry in parameter_list {
+                let entry = match resolver.resolve(entry) {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        continue;
+                    }
+                };
+                let existing = parameters.iter().position(|parameter| parameter["name"] == entry["name"] && parameter["in"] == entry["in"]);
+                match existing {
+                    Some(index) => parameters[index] = entry,
+                    None => parameters.push(entry)
+                }
+            }
+        }
+    }
+    parameters
+}
+
+fn get_paths(resolver : &Resolver, format : &dyn Format, paths : &Yaml, security : &Yaml) -> Vec<Request>{
+    if let Yaml::Hash(ref h) = paths {
+        let mut result = Vec::new();
+        for (k,v) in h {
+            let path = k.as_str().unwrap();
+            let path_item = resolver.resolve(v).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                &Yaml::BadValue
+            });
+            let mut methods = Vec::new();
+            if let Yaml::Hash(ref operations) = path_item {
+                for (method,details) in operations {
+                    let the_method = method.as_str().unwrap();
+                    //a path item also holds summary, description, servers and shared parameters
+                    if !HTTP_METHODS.contains(&the_method) {
+                        continue;
+                    }
+                    methods.push(get_operation(resolver, format, path, the_method, path_item, details, security));
+                }
+            }
+            result.push(Request::new(format, String::from(path), String::from(path_item["summary"].as_str().unwrap_or("")), format.markdown(path_item["description"].as_str().unwrap_or("")), get_servers(format, &path_item["servers"]), methods));
+        }
+        result
+    } else {
+        Vec::new()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_operation<'a>(resolver : &Resolver<'a>, format : &dyn Format, path : &str, method : &str, path_item : &'a Yaml, details : &'a Yaml, security : &Yaml) -> Method {
+    let mut path_params = PathParameter::new();
+    let mut headers = RequestHeader::new();
+    let mut querys = QueryParameter::new();
+    let mut cookies = CookieParameter::new();
+    let mut responses = Vec::new();
+    //loop over parameters
+    for entry in get_parameters(resolver, path_item, details) {
+        let type_name = get_type_name(resolver, entry).unwrap_or_else(|_| String::from("unknown type"));
+        //path parameters are always required, even if the spec does not say so
+        let required = entry["required"].as_bool().unwrap_or(entry["in"].as_str() == Some("path"));
+        let param = Field::new(format, String::from(entry["name"].as_str().unwrap_or("")),type_name, required,format.markdown_item(entry["description"].as_str().unwrap_or("")), get_example(resolver, entry));
+        match entry["in"] {
+            Yaml::String(ref val) if val.contains("path") => {
+                path_params.add(param);
+            },
+            Yaml::String(ref val) if val.contains("query") => {
+                querys.add(param);
+            },
+             Yaml::String(ref val) if val.contains("header") => {
+                headers.add(param);
+            },
+            Yaml::String(ref val) if val.contains("cookie") => {
+                cookies.add(param);
+            },
+            _ => {}
+        };
+    }
+    //add a request body if needed
+    let request_body = resolver.resolve(&details["requestBody"]).unwrap_or(&Yaml::BadValue);
+    let bodys = if let Yaml::Hash(_) = request_body {
+        Some(RequestBody::new(format.markdown(request_body["description"].as_str().unwrap_or("")), request_body["required"].as_bool().unwrap_or(false), get_content(resolver, format, &request_body["content"])))
+    } else {
+        None
+    };
+    //add the response if needed
+    if let Yaml::Hash(ref inner) = &details["responses"] {
+        for (response_code, response_node) in inner {
+            let response_node = match resolver.resolve(response_node) {
+                Ok(response_node) => response_node,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    continue;
+                }
+            };
+            let mut response = Response::new();
+            let response_code_string = match response_code {
+                Yaml::Integer(code) => code.to_string(),
+                _ => String::from(response_code.as_str().unwrap_or("default"))
+            };
+            if !response_node["schema"].is_badvalue() {
+                //swagger 2 declares the schema directly, the media types are listed in produces
+                let type_name = get_type_name(resolver, response_node).unwrap_or_else(|_| String::from("unknown type"));
+                let media_type = details["produces"][0].as_str().unwrap_or("application/json");
+                let schema = Field::new(format, String::new(), type_name, false, String::new(), String::new());
+                response.set_content(vec![MediaType::new(format, String::from(media_type), Some(schema), Vec::new(), Vec::new())]);
+            } else {
+                response.set_content(get_content(resolver, format, &response_node["content"]));
+            }
+            response.set_description(format.markdown(response_node["description"].as_str().unwrap_or("")));
+            response.set_headers(get_headers(resolver, format, &response_node["headers"]));
+            response.set_links(get_links(resolver, format, &response_node["links"]));
+            response.set_pointer(pointer(&["paths", path, method, "responses", &response_code_string]));
+            response.set_status_code(format, response_code_string);
+            responses.push(response);
+        }
     }
-    let class_parts = long_type_name.split(".");
-    let mut class_name = String::new();
-    for part in class_parts {
-        let first_char = part.chars().next().unwrap();
-        if  first_char.is_uppercase(){
-            class_name.push_str(".");
-            class_name.push_str(part);
+    let mut tags = Vec::new();
+    if let Yaml::Array(ref operation_tags) = details["tags"] {
+        tags.extend(operation_tags.iter().filter_map(|tag| tag.as_str()).map(String::from));
+    }
+    //the security of the document applies unless the operation declares its own, security: [] removes it
+    let security = if details["security"].is_badvalue() { security } else { &details["security"] };
+    let mut the_method = Method::new(format, String::from(method), String::from(path), String::from(details["summary"].as_str().unwrap_or("")),  format.markdown(details["description"].as_str().unwrap_or("")), tags, get_servers(format, &details["servers"]), if path_params.required() {Option::Some(path_params)} else { Option::None}, if headers.required() {Option::Some(headers)} else { Option::None}, if querys.required() {Option::Some(querys)} else { Option::None}, if cookies.required() {Option::Some(cookies)} else { Option::None}, bodys, responses);
+    the_method.set_operation_id(format, String::from(details["operationId"].as_str().unwrap_or("")));
+    the_method.set_security(get_security(format, security), security.as_vec().map(|requirements| requirements.is_empty()).unwrap_or(false));
+    the_method
+}
+
+//every media type of a request body or a response with its schema, examples and encoding
+fn get_content(resolver : &Resolver, format : &dyn Format, content : &Yaml) -> Vec<MediaType> {
+    let mut result = Vec::new();
+    if let Yaml::Hash(ref media_types) = content {
+        for (media_type, media) in media_types {
+            let schema = if media["schema"].is_badvalue() {
+                None
+            } else {
+                let type_name = get_type_name(resolver, media).unwrap_or_else(|_| String::from("unknown type"));
+                Some(Field::new(format, String::new(), type_name, false, String::new(), String::new()))
+            };
+            let media_type = yaml_to_string(media_type);
+            let is_json = example::is_json_media_type(&media_type);
+            let mut examples = Vec::new();
+            if !media["example"].is_badvalue() {
+                examples.push(Example::new(format, String::new(), String::new(), String::new(), example::pretty(&example::to_json(&media["example"]), is_json), String::new()));
+            }
+            if let Yaml::Hash(ref named_examples) = media["examples"] {
+                for (name, named_example) in named_examples {
+                    let named_example = match resolver.resolve(named_example) {
+                        Ok(named_example) => named_example,
+                        Err(err) => {
+                            eprintln!("{}", err);
+                            continue;
+                        }
+                    };
+                    let value = if named_example["value"].is_badvalue() { String::new() } else { example::pretty(&example::to_json(&named_example["value"]), is_json) };
+                    examples.push(Example::new(format, yaml_to_string(name), String::from(named_example["summary"].as_str().unwrap_or("")), format.markdown_item(named_example["description"].as_str().unwrap_or("")), value, String::from(named_example["externalValue"].as_str().unwrap_or(""))));
+                }
+            }
+            //without examples one is made up from the schema, as far as it can be written as JSON
+            if examples.is_empty() && is_json {
+                let generated = example::from_schema(resolver, &media["schema"]);
+                if !generated.is_null() {
+                    let mut the_example = Example::new(format, String::new(), String::new(), String::new(), example::pretty(&generated, true), String::new());
+                    the_example.set_generated(true);
+                    examples.push(the_example);
+                }
+            }
+            let mut encoding = Vec::new();
+            if let Yaml::Hash(ref properties) = media["encoding"] {
+                for (property, property_encoding) in properties {
+                    encoding.push(get_encoding(resolver, format, &yaml_to_string(property), property_encoding));
+                }
+            }
+            //form bodies are documented part by part, each with its own encoding
+            let mut parts = Vec::new();
+            if media_type.starts_with("multipart/") || media_type == "application/x-www-form-urlencoded" {
+                for field in get_form_fields(resolver, format, &media["schema"]) {
+                    let index = encoding.iter().position(|part_encoding| part_encoding.property == field.field);
+                    parts.push(FormPart::new(field, index.map(|index| encoding.remove(index))));
+                }
+            }
+            let mut the_media_type = MediaType::new(format, media_type, schema, examples, encoding);
+            the_media_type.set_parts(parts);
+            result.push(the_media_type);
         }
+    }
+    result
+}
+
+//the properties of a form body, binary and other formats are noted with the type
+fn get_form_fields(resolver : &Resolver, format : &dyn Format, schema : &Yaml) -> Vec<Field> {
+    let schema = match resolver.resolve(schema) {
+        Ok(schema) => schema,
+        Err(err) => {
+            eprintln!("{}", err);
+            return Vec::new();
+        }
+    };
+    let mut collected = CollectedProperties {
+        properties : Vec::new(),
+        required : Vec::new(),
+        parents : Vec::new()
     };
-    //skip the first character since it is a dot
-    String::from(&class_name[1..])
+    collect_properties(resolver, schema, None, &mut collected, &mut Vec::new());
+    let mut fields = Vec::new();
+    for (prop_name, prop_node, _) in collected.properties {
+        let mut type_name = get_type_name_without_schema(resolver, prop_node).unwrap_or_else(|_| String::from("unknown type"));
+        let prop_node = resolver.resolve(prop_node).unwrap_or(prop_node);
+        if let Some(data_format) = prop_node["format"].as_str().or_else(|| prop_node["items"]["format"].as_str()) {
+            type_name = format!("{} ({})", type_name, data_format);
+        }
+        let description = format.markdown_item(prop_node["description"].as_str().unwrap_or(""));
+        let is_required = collected.required.contains(&prop_name);
+        fields.push(Field::new(format, prop_name, type_name, is_required, description, get_example(resolver, prop_node)));
+    }
+    fields
+}
+
+//the headers of a response or of a part of a multipart body
+fn get_headers(resolver : &Resolver, format : &dyn Format, headers : &Yaml) -> Vec<Field> {
+    let mut result = Vec::new();
+    if let Yaml::Hash(ref headers) = headers {
+        for (name, header) in headers {
+            let header = match resolver.resolve(header) {
+                Ok(header) => header,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    continue;
+                }
+            };
+            let type_name = get_type_name(resolver, header).unwrap_or_else(|_| String::from("unknown type"));
+            result.push(Field::new(format, yaml_to_string(name), type_name, header["required"].as_bool().unwrap_or(false), format.markdown_item(header["description"].as_str().unwrap_or("")), get_example(resolver, header)));
+        }
+    }
+    result
+}
+
+//links from a response to operations which can use values of the response
+fn get_links(resolver : &Resolver, format : &dyn Format, links : &Yaml) -> Vec<ResponseLink> {
+    let mut result = Vec::new();
+    if let Yaml::Hash(ref links) = links {
+        for (name, link) in links {
+            let link = match resolver.resolve(link) {
+                Ok(link) => link,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    continue;
+                }
+            };
+            let mut parameters = Vec::new();
+            if let Yaml::Hash(ref link_parameters) = link["parameters"] {
+                for (parameter, expression) in link_parameters {
+                    parameters.push(LinkParameter::new(format, yaml_to_string(parameter), yaml_to_string(expression)));
+                }
+            }
+            let mut the_link = ResponseLink::new(format, yaml_to_string(name), String::from(link["operationId"].as_str().unwrap_or("")), String::from(link["operationRef"].as_str().unwrap_or("")), format.markdown_item(link["description"].as_str().unwrap_or("")), parameters);
+            the_link.set_request_body(format, yaml_to_string(&link["requestBody"]));
+            result.push(the_link);
+        }
+    }
+    result
+}
+
+fn get_encoding(resolver : &Resolver, format : &dyn Format, property : &str, encoding : &Yaml) -> Encoding {
+    let headers = get_headers(resolver, format, &encoding["headers"]);
+    let explode = encoding["explode"].as_bool().map(|explode| explode.to_string()).unwrap_or_default();
+    Encoding::new(format, String::from(property), String::from(encoding["contentType"].as_str().unwrap_or("")), headers, String::from(encoding["style"].as_str().unwrap_or("")), explode, encoding["allowReserved"].as_bool().unwrap_or(false))
+}
+
+static UNTAGGED : &str = "Untagged";
+
+//group the operations by their tags, in the order the tags are declared at the top level of the document
+fn get_tags(format : &dyn Format, declared_tags : &Yaml, requests : Vec<Request>) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    if let Yaml::Array(ref declared_tags) = declared_tags {
+        for tag in declared_tags {
+            let external_docs = if let Yaml::String(url) = &tag["externalDocs"]["url"] {
+                Some(ExternalDocs::new(format, url.clone(), String::from(tag["externalDocs"]["description"].as_str().unwrap_or(url))))
+            } else {
+                None
+            };
+            tags.push(Tag::new(format, String::from(tag["name"].as_str().unwrap_or("")), format.markdown(tag["description"].as_str().unwrap_or("")), external_docs));
+        }
+    }
+    let any_tagged = requests.iter().flat_map(|request| request.methods.iter()).any(|method| !method.tags.is_empty());
+    for request in requests {
+        for method in request.methods.iter() {
+            let untagged = vec![String::from(UNTAGGED)];
+            let method_tags = if method.tags.is_empty() { &untagged } else { &method.tags };
+            for tag_name in method_tags {
+                let index = match tags.iter().position(|tag| tag.name == format.text(tag_name)) {
+                    Some(index) => index,
+                    None => {
+                        tags.push(Tag::new(format, tag_name.clone(), String::new(), None));
+                        tags.len() - 1
+                    }
+                };
+                tags[index].add(&request, method.clone());
+            }
+        }
+    }
+    //operations without tags come last, unless the document does not use tags at all
+    if let Some(index) = tags.iter().position(|tag| tag.name == UNTAGGED) {
+        let mut untagged = tags.remove(index);
+        if !any_tagged {
+            untagged.name = String::from("Web Service");
+        }
+        tags.push(untagged);
+    }
+    tags.retain(|tag| !tag.requests.is_empty());
+    tags
+}
+
+impl Documentation {
+    pub fn new(document_root : &Yaml, format : &dyn Format, config : &Config) -> Documentation{
+        let resolver = Resolver::new(document_root);
+        let title = document_root["info"]["title"].as_str().unwrap_or("");
+        let info = get_info(format, &document_root["info"]);
+        let introduction = match (document_root["info"]["description"].as_str(), &config.introduction) {
+            (Some(description), _) if !description.trim().is_empty() => info.description.clone(),
+            (_, Some(Introduction::Markdown(markdown))) => format.markdown(markdown),
+            (_, Some(Introduction::Raw(raw))) => raw.clone(),
+            _ => String::new()
+        };
+        let company = Company::new(format, config);
+        let contact_name = document_root["info"]["contact"]["name"].as_str().unwrap_or("");
+        let author = if config.company.is_empty() { contact_name } else { config.company.as_str() };
+        let servers = get_servers(format, &document_root["servers"]);
+
+        let security_schemes = get_security_schemes(&resolver, format, &document_root["components"]["securitySchemes"]);
+        let security = get_security(format, &document_root["security"]);
+
+        let requests = get_paths(&resolver, format, &document_root["paths"], &document_root["security"]);
+        let mut tags = get_tags(format, &document_root["tags"], requests);
+        let (root_models, models_key) = if document_root["components"]["schemas"].is_badvalue() {
+            (&document_root["definitions"], vec!["definitions"])
+        } else {
+            (&document_root["components"]["schemas"], vec!["components", "schemas"])
+        };
+        let mut schemas = if let Yaml::Hash(ref h) = root_models {
+            let mut result = Vec::new();
+            for (k,v) in h {
+                let model = match resolver.resolve(v) {
+                    Ok(model) => model,
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        continue;
+                    }
+                };
+                let model_name = yaml_to_string(k);
+                let mut nested = Vec::new();
+                result.push(get_schema(&resolver, format, shorten_type_name(&model_name), model, &mut nested));
+                result.append(&mut nested);
+                //inline models are traced back to the model they are defined in
+                let model_pointer = pointer(&[models_key.as_slice(), &[model_name.as_str()]].concat());
+                for schema in result.iter_mut().rev().take_while(|schema| schema.pointer.is_empty()) {
+                    schema.set_pointer(model_pointer.clone());
+                }
+            }
+            result
+        } else {
+            Vec::new()
+        };
+        //only types which are documented as a model of their own can be linked
+        let models : Vec<String> = schemas.iter().map(|schema| schema.label.clone()).collect();
+        let methods = tags.iter_mut().flat_map(|tag| tag.requests.iter_mut()).flat_map(|request| request.methods.iter_mut());
+        for field in methods.flat_map(|method| method.fields_mut()).chain(schemas.iter_mut().flat_map(|schema| schema.fields_mut())) {
+            field.is_model = models.contains(&field.pure_type);
+        }
+        //the models are checked against the specification when the configuration is loaded
+        let errors : Vec<SharedError> = config.errors.iter().map(|(status, model)| SharedError::new(format, HttpStatus::parse(status), &shorten_type_name(model))).collect();
+        let responses = tags.iter_mut().flat_map(|tag| tag.requests.iter_mut()).flat_map(|request| request.methods.iter_mut()).flat_map(|method| method.responses.iter_mut());
+        for response in responses {
+            if let Some(error) = find_shared_error(&errors, &response.status_code) {
+                response.set_shared_error(error);
+            }
+        }
+        Documentation {
+            title : format.text(title),
+            author : format.text(author),
+            info,
+            introduction,
+            company,
+            has_servers : !servers.is_empty(),
+            servers,
+            has_security_schemes : !security_schemes.is_empty(),
+            security_schemes,
+            has_security : !security.is_empty(),
+            security,
+            has_errors : !errors.is_empty(),
+            errors,
+            tags,
+            schemas
+        }
+    }
+}
+
+//the shared error model of a status, an exact code takes precedence over its range and the default
+fn find_shared_error<'a>(errors : &'a [SharedError], status_code : &str) -> Option<&'a SharedError> {
+    let status = HttpStatus::parse(status_code);
+    if !status.is_error() {
+        return None;
+    }
+    let range = format!("{}XX", &status.code[..1]);
+    errors.iter().find(|error| error.status_code == status.code)
+        .or_else(|| errors.iter().find(|error| error.status_code == range))
+        .or_else(|| errors.iter().find(|error| error.status_code == "default"))
+}
+
+fn get_servers(format : &dyn Format, servers : &Yaml) -> Vec<Server> {
+    let mut result = Vec::new();
+    if let Yaml::Array(ref servers) = servers {
+        for server in servers {
+            let mut variables = Vec::new();
+            if let Yaml::Hash(ref server_variables) = server["variables"] {
+                for (name, variable) in server_variables {
+                    let values : Vec<String> = match variable["enum"] {
+                        Yaml::Array(ref values) => values.iter().map(yaml_to_string).collect(),
+                        _ => Vec::new()
+                    };
+                    variables.push(ServerVariable::new(format, yaml_to_string(name), yaml_to_string(&variable["default"]), format.markdown_item(variable["description"].as_str().unwrap_or("")), values));
+                }
+            }
+            result.push(Server::new(format, String::from(server["url"].as_str().unwrap_or("")), format.markdown_item(server["description"].as_str().unwrap_or("")), variables));
+        }
+    }
+    result
+}
+
+static OAUTH_FLOWS : [(&str, &str); 4] = [("implicit", "Implicit"), ("password", "Password"), ("clientCredentials", "Client credentials"), ("authorizationCode", "Authorization code")];
+
+fn get_security_schemes(resolver : &Resolver, format : &dyn Format, schemes : &Yaml) -> Vec<SecurityScheme> {
+    let mut result = Vec::new();
+    if let Yaml::Hash(ref schemes) = schemes {
+        for (name, scheme) in schemes {
+            let name = yaml_to_string(name);
+            let scheme = match resolver.resolve(scheme) {
+                Ok(scheme) => scheme,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    continue;
+                }
+            };
+            let kind = match (scheme["type"].as_str().unwrap_or(""), scheme["scheme"].as_str().map(|scheme| scheme.to_lowercase())) {
+                ("apiKey", _) => String::from("API key"),
+                ("http", Some(ref http_scheme)) if http_scheme == "basic" => String::from("HTTP basic authentication"),
+                ("http", Some(ref http_scheme)) if http_scheme == "bearer" => String::from("HTTP bearer token"),
+                ("http", Some(http_scheme)) => format!("HTTP {} authentication", http_scheme),
+                ("oauth2", _) => String::from("OAuth 2.0"),
+                ("openIdConnect", _) => String::from("OpenID Connect"),
+                ("mutualTLS", _) => String::from("Mutual TLS"),
+                (other, _) => String::from(other)
+            };
+            let location = match scheme["in"].as_str().unwrap_or("") {
+                "query" => "query parameter",
+                other => other
+            };
+            let mut flows = Vec::new();
+            for (key, flow_name) in OAUTH_FLOWS.iter() {
+                let flow = &scheme["flows"][*key];
+                if let Yaml::Hash(_) = flow {
+                    let mut scopes = Vec::new();
+                    if let Yaml::Hash(ref flow_scopes) = flow["scopes"] {
+                        for (scope, description) in flow_scopes {
+                            scopes.push(Scope::new(format, yaml_to_string(scope), format.markdown_item(description.as_str().unwrap_or(""))));
+                        }
+                    }
+                    flows.push(OAuthFlow::new(format, String::from(*flow_name), String::from(flow["authorizationUrl"].as_str().unwrap_or("")), String::from(flow["tokenUrl"].as_str().unwrap_or("")), String::from(flow["refreshUrl"].as_str().unwrap_or("")), scopes));
+                }
+            }
+            let mut security_scheme = SecurityScheme::new(format, name.clone(), kind, format.markdown(scheme["description"].as_str().unwrap_or("")), flows);
+            security_scheme.set_pointer(pointer(&["components", "securitySchemes", &name]));
+            security_scheme.set_api_key(format, String::from(scheme["name"].as_str().unwrap_or("")), String::from(location));
+            security_scheme.set_bearer_format(format, String::from(scheme["bearerFormat"].as_str().unwrap_or("")));
+            security_scheme.set_open_id_connect_url(format, String::from(scheme["openIdConnectUrl"].as_str().unwrap_or("")));
+            result.push(security_scheme);
+        }
+    }
+    result
+}
+
+//the alternatives of a security requirement, an empty requirement makes the authentication optional
+fn get_security(format : &dyn Format, security : &Yaml) -> Vec<SecurityRequirement> {
+    let mut result = Vec::new();
+    if let Yaml::Array(ref requirements) = security {
+        for requirement in requirements {
+            let mut schemes = Vec::new();
+            if let Yaml::Hash(ref requirement) = requirement {
+                for (name, scopes) in requirement {
+                    let scopes : Vec<String> = match scopes {
+                        Yaml::Array(ref scopes) => scopes.iter().map(yaml_to_string).collect(),
+                        _ => Vec::new()
+                    };
+                    schemes.push(RequiredScheme::new(format, yaml_to_string(name), scopes, !schemes.is_empty()));
+                }
+            }
+            result.push(SecurityRequirement::new(schemes, !result.is_empty()));
+        }
+    }
+    result
+}
+
+fn get_info(format : &dyn Format, info : &Yaml) -> Info {
+    let contact = if let Yaml::Hash(_) = info["contact"] {
+        let contact = &info["contact"];
+        Some(Contact::new(format, String::from(contact["name"].as_str().unwrap_or("")), String::from(contact["url"].as_str().unwrap_or("")), String::from(contact["email"].as_str().unwrap_or(""))))
+    } else {
+        None
+    };
+    let license = if let Yaml::String(name) = &info["license"]["name"] {
+        Some(License::new(format, name.clone(), String::from(info["license"]["url"].as_str().unwrap_or(""))))
+    } else {
+        None
+    };
+    Info::new(format, yaml_to_string(&info["version"]), format.markdown(info["description"].as_str().unwrap_or("")), String::from(info["termsOfService"].as_str().unwrap_or("")), contact, license)
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct Server {
+    url : String,
+    description : String,
+    variables : Vec<ServerVariable>,
+    has_variables : bool
+}
+impl Server {
+    pub fn new(format : &dyn Format, url : String, description : String, variables : Vec<ServerVariable>) -> Server {
+        let has_variables = !variables.is_empty();
+        Server {
+            url : format.text(&url),
+            description,
+            variables,
+            has_variables
+        }
+    }
+}
+#[derive(Serialize, Debug, Clone)]
+struct ServerVariable {
+    name : String,
+    default : String,
+    description : String,
+    //the allowed values, if they are restricted
+    values : String,
+    has_values : bool
+}
+impl ServerVariable {
+    pub fn new(format : &dyn Format, name : String, default : String, description : String, values : Vec<String>) -> ServerVariable {
+        ServerVariable {
+            name : format.text(&name),
+            default : format.text(&default),
+            description,
+            has_values : !values.is_empty(),
+            values : format.text(&values.join(", "))
+        }
+    }
+}
+#[derive(Serialize, Debug)]
+struct SecurityScheme {
+    pointer : String,
+    name : String,
+    label : String,
+    //the type of the scheme in words, e.g. HTTP bearer token
+    kind : String,
+    description : String,
+    //name and location of an API key
+    parameter : String,
+    location : String,
+    bearer_format : String,
+    open_id_connect_url : String,
+    flows : Vec<OAuthFlow>,
+    has_flows : bool
+}
+impl SecurityScheme {
+    pub fn new(format : &dyn Format, name : String, kind : String, description : String, flows : Vec<OAuthFlow>) -> SecurityScheme {
+        SecurityScheme {
+            pointer : String::new(),
+            label : label(&name),
+            name : format.text(&name),
+            kind : format.text(&kind),
+            description,
+            parameter : String::new(),
+            location : String::new(),
+            bearer_format : String::new(),
+            open_id_connect_url : String::new(),
+            has_flows : !flows.is_empty(),
+            flows
+        }
+    }
+    pub fn set_pointer(&mut self, pointer : String) {
+        self.pointer = pointer;
+    }
+    pub fn set_api_key(&mut self, format : &dyn Format, parameter : String, location : String) {
+        self.parameter = format.text(&parameter);
+        self.location = format.text(&location);
+    }
+    pub fn set_bearer_format(&mut self, format : &dyn Format, bearer_format : String) {
+        self.bearer_format = format.text(&bearer_format);
+    }
+    pub fn set_open_id_connect_url(&mut self, format : &dyn Format, url : String) {
+        self.open_id_connect_url = format.url(&url);
+    }
+}
+#[derive(Serialize, Debug)]
+struct OAuthFlow {
+    name : String,
+    authorization_url : String,
+    token_url : String,
+    refresh_url : String,
+    scopes : Vec<Scope>,
+    has_scopes : bool
+}
+impl OAuthFlow {
+    pub fn new(format : &dyn Format, name : String, authorization_url : String, token_url : String, refresh_url : String, scopes : Vec<Scope>) -> OAuthFlow {
+        OAuthFlow {
+            name : format.text(&name),
+            authorization_url : format.url(&authorization_url),
+            token_url : format.url(&token_url),
+            refresh_url : format.url(&refresh_url),
+            has_scopes : !scopes.is_empty(),
+            scopes
+        }
+    }
+}
+#[derive(Serialize, Debug)]
+struct Scope {
+    name : String,
+    description : String
+}
+impl Scope {
+    pub fn new(format : &dyn Format, name : String, description : String) -> Scope {
+        Scope {
+            name : format.text(&name),
+            description
+        }
+    }
+}
+//one alternative of the security requirements, all of its schemes have to be used together
+#[derive(Serialize, Debug, Clone)]
+struct SecurityRequirement {
+    schemes : Vec<RequiredScheme>,
+    //an empty requirement, authentication is optional
+    is_anonymous : bool,
+    //whether it follows another alternative, templates use it to write "or"
+    alternative : bool
+}
+impl SecurityRequirement {
+    pub fn new(schemes : Vec<RequiredScheme>, alternative : bool) -> SecurityRequirement {
+        SecurityRequirement {
+            is_anonymous : schemes.is_empty(),
+            schemes,
+            alternative
+        }
+    }
+}
+#[derive(Serialize, Debug, Clone)]
+struct RequiredScheme {
+    name : String,
+    label : String,
+    scopes : String,
+    has_scopes : bool,
+    //whether it follows another scheme of the same requirement, templates use it to write "and"
+    combined : bool
+}
+impl RequiredScheme {
+    pub fn new(format : &dyn Format, name : String, scopes : Vec<String>, combined : bool) -> RequiredScheme {
+        RequiredScheme {
+            label : label(&name),
+            name : format.text(&name),
+            has_scopes : !scopes.is_empty(),
+            scopes : format.text(&scopes.join(", ")),
+            combined
+        }
+    }
 }
 
-fn get_type_name_without_schema(prop : &Yaml) -> Result<String,TypeNotFound> {
-    if let Yaml::String(val) = &prop["type"] {
-        match val.as_str() {
-        "array" => {
-            let innerType = if let Yaml::String(innerProp) = &prop["items"]["type"]
-            {
-               String::from(innerProp.as_str())
-            } else if let Yaml::String(innerProp) = &prop["items"]["$ref"]{
-                String::from( shorten_type_name(&(innerProp.as_str().replace("#/components/schemas/", ""))))
-            } else {
-                String::from("unknown property")
-            };
-            Ok( String::from(format!("{}[]", innerType)))
-        },
-        "object" => {
-            //for responses we either have a map, or a field holding the array
-            let innerType = if let Yaml::String(addProp) = &prop["additionalProperties"]["$ref"] {
-                String::from(addProp.as_str().replace("#/components/schemas/", ""))
-            } else if let Yaml::String(addProp) = &prop["additionalProperties"]["type"] {
-                String::from(addProp.as_str())
-            } else {
-                String::from("unknown type")
-            };
-            Ok(String::from(format!("Map<string,{}>", shorten_type_name(&innerType))))
-        },
-        _ => {
-            Ok(String::from(val.as_str()))
+#[derive(Serialize, Debug)]
+struct Info {
+    version : String,
+    description : String,
+    terms_of_service : String,
+    contact : Option<Contact>,
+    license : Option<License>
+}
+impl Info {
+    pub fn new(format : &dyn Format, version : String, description : String, terms_of_service : String, contact : Option<Contact>, license : Option<License>) -> Info {
+        Info {
+            version : format.text(&version),
+            description,
+            terms_of_service : format.url(&terms_of_service),
+            contact,
+            license
         }
     }
-    } else if let Yaml::String(val) = &prop["$ref"] {
-        Ok(shorten_type_name(&(val.as_str().replace("#/components/schemas/", ""))))
-    } else {
-        Err(TypeNotFound {})
+}
+#[derive(Serialize, Debug)]
+struct Contact {
+    name : String,
+    url : String,
+    email : String,
+    //the link target of the email, escaped as url instead of text
+    mailto : String
+}
+impl Contact {
+    pub fn new(format : &dyn Format, name : String, url : String, email : String) -> Contact {
+        Contact {
+            name : format.text(&name),
+            url : format.url(&url),
+            mailto : format.url(&format!("mailto:{}", email)),
+            email : format.text(&email)
+        }
     }
 }
-
-fn get_paths(paths : &Yaml) -> Vec<Request>{
-if let Yaml::Hash(ref h) = paths {
-            let mut result = Vec::new();
-            for (k,v) in h {
-                let mut headers = RequestHeader::new();
-                let mut querys = QueryParameter::new();
-                let mut bodys = ResponseBody::new();
-                let mut responses = Vec::new();
-                let mut descriptionString = String::new();
-                let methods = if let Yaml::Hash(ref v) = paths[k.as_str().unwrap()] {
-                    let mut arr = Vec::new();
-                    for (method,details) in v {
-                        let theMethod = method.as_str().unwrap();
-                       
-                        let meth = Method::new(String::from(theMethod), String::from(k.as_str().unwrap()), String::from(paths[k.as_str().unwrap()][theMethod]["summary"].as_str().unwrap_or("")),  markdown_to_latex(paths[k.as_str().unwrap()][theMethod]["description"].as_str().unwrap_or("")));
-                        //println!("{}", meth.description);
-                        descriptionString = String::from(meth.description.as_str());
-                        arr.push(meth);
-                        //loop over parameters
-                        let params = &paths[k.as_str().unwrap()][theMethod]["parameters"];
-                        if let Yaml::Array(ref parameter_list) = params {
-                           for entry in parameter_list {
-                               //println!("{:?}", entry);
-                               let typeName = get_typeName(&entry).unwrap_or(String::from("unknown\\_type"));
-                                let mut example = String::new();
-                                        if let Yaml::String(val) = &entry["example"] {
-                                            example = String::from(val.as_str());
-                                        }
-                                let param = Field::new(String::from(entry["name"].as_str().unwrap_or("").replace("_", "\\_")),typeName, entry["required"].as_bool().unwrap_or(false),markdown_to_latex(entry["description"].as_str().unwrap_or("")), example);
-                               // println!("{}", param.description);
-                                match entry["in"] {
-                                    Yaml::String(ref val) if val.contains("query") => {
-                                        querys.add(param);
-                                    },
-                                     Yaml::String(ref val) if val.contains("header") => {
-                                        let mut example = String::new();
-                                        if let Yaml::String(val) = &entry["example"] {
-                                            example = String::from(val.as_str());
-                                        }
-                                        headers.add(param);
-                                    },
-                                    _ => {}
-                                };
-                           }
-                        }
-                        //add a request body if needed
-                        if let Yaml::Hash(ref inner) = details["requestBody"] {
-                            let typeName = get_typeName(&details["requestBody"]["content"]["application/json"]).unwrap_or(String::from("unknown type"));
-                            let mut example = String::new();
-                            if let Yaml::String(val) = &details["requestBody"]["example"] {
-                                example = String::from(val.as_str());
-                            }
-                            let theField = Field::new(String::from(""), typeName, details["requestBody"]["required"].as_bool().unwrap_or(false),markdown_to_latex(details["requestBody"]["description"].as_str().unwrap_or("N/A")), example);
-                            bodys.add(theField);
-                        }
-                        //add the response if needed
-                        if let Yaml::Hash(ref inner) = &details["responses"] {
-                            for (responseCode, responseNode) in inner {
-                                let mut response = Response::new();
-                                let mut typeName = String::from("");
-                                let responseCodeString = responseCode.as_str().unwrap();
-                                if !&details["responses"][responseCodeString]["content"]["application/json"].is_badvalue() {
-                                    typeName = get_typeName(&details["responses"][responseCodeString]["content"]["application/json"]).unwrap_or(String::from("unkown type"));
-                                } else if !&details["responses"][responseCodeString]["schema"].is_badvalue() {
-                                    //we have a global defined content type
-                                    typeName = get_typeName(&details["responses"][responseCodeString]).unwrap_or(String::from("unknown type"));
-                                }
-                                else {
-                                    if let Yaml::Hash(innerMap) = &details["responses"][responseCodeString]["content"] {
-                                        for (k,v) in innerMap {
-                                            response.set_content_type(String::from(k.as_str().unwrap()));
-                                            break;
-                                        }
-                                    }
-                                
-                                }
-                                let fieldName = "*";
-                                let required = if let Yaml::Boolean(val) = &details["requestBody"]["required"] {
-                                    *val
-                                } else {
-                                    false
-                                };
-                                let mut example = String::new();
-                                if let Yaml::String(val) = &details["responses"][responseCodeString]["example"] {
-                                    example = String::from(val.as_str());
-                                }
-                                let theField = Field::new(String::from(""), typeName, required,markdown_to_latex(details["responses"][responseCodeString]["description"].as_str().unwrap()), example);
-                                response.set_description(markdown_to_latex(details["responses"][responseCodeString]["description"].as_str().unwrap()));
-                                response.set_status_code(responseCodeString.to_string());
-                                response.add(theField);
-                                responses.push(response);
-                            }
-                        }
-                    }
-                    arr
-                }
-                else {
-                    Vec::new()
-                };
-            
-                result.push(Request::new(String::from(k.as_str().unwrap()), methods, descriptionString, if headers.required() {Option::Some(headers)} else { Option::None}, if querys.required() {Option::Some(querys)} else { Option::None}, if bodys.required() {Option::Some(bodys)} else { Option::None}, responses));
-            }
-            result
-        } else {
-            panic!("Could not find paths");
+#[derive(Serialize, Debug)]
+struct License {
+    name : String,
+    url : String,
+    //mustache renders inverted sections only for false, null and empty lists, not for empty strings
+    has_url : bool
+}
+impl License {
+    pub fn new(format : &dyn Format, name : String, url : String) -> License {
+        let url = format.url(&url);
+        License {
+            name : format.text(&name),
+            has_url : !url.is_empty(),
+            url
         }
+    }
 }
-
-impl Documentation {
-    pub fn new(documentRoot : &Yaml) -> Documentation{
-        let title = documentRoot["info"]["title"].as_str().unwrap();
-        let host = documentRoot["servers"][0]["url"].as_str().unwrap_or("-");
-        
-        let requests = get_paths(&documentRoot["paths"]);
-        let rootModels = if documentRoot["components"]["schemas"].is_badvalue() {
-            &documentRoot["definitions"]
-        } else {
-            &documentRoot["components"]["schemas"]
-        };
-        let schemas = if let Yaml::Hash(ref h) = rootModels {
-            let mut result = Vec::new();
-            for (k,v) in h {
-                let mut properties = Vec::new();
-                let mut enumValues = Vec::new();
-                let mut required = Vec::new();
-                if let Yaml::Array(ref required_properties) = documentRoot["components"]["schemas"][k.as_str().unwrap()]["required"] {
-                    for required_property in required_properties {
-                        required.push(required_property.as_str().unwrap());
-                       // println!("{:?}", required_property);
-                    }
-                }
-                if let Yaml::Hash(ref inner) = documentRoot["components"]["schemas"][k.as_str().unwrap()]["properties"] {
-                    for (propName, propNode) in inner {
-                        let fieldName = shorten_type_name(&propName.as_str().unwrap().replace("_", "\\_"));
-                        let type_name =  get_type_name_without_schema(&propNode).unwrap_or(String::from("unknown type"));
-                        let is_property_required = required.contains(&propName.as_str().unwrap());
-                        let mut description = String::from("");
-                        let mut example = String::from("");
-                        if let Yaml::String(val) = &propNode["description"] {
-                            description = markdown_to_latex(val.as_str());
-                        }
-                        if let Yaml::String(val) = &propNode["example"] {
-                            example = String::from(val.as_str());
-                        }
-                        let theField = Field::new(fieldName,type_name,is_property_required,description, example);
-                        properties.push(theField);
-                    }
-                } else if let Yaml::Array(ref inner) = documentRoot["components"]["schemas"][k.as_str().unwrap()]["enum"] {
-                    for enumName in inner {
-                        enumValues.push(Field::new(String::from(enumName.as_str().unwrap().replace("_","\\_")), String::from(""), false, String::from(""), String::from("")));
-                    }
-                }
-                result.push(Schema::new(shorten_type_name(k.as_str().unwrap()), properties, enumValues));
-            }
-            result
-        } else {
-            panic!("Could not find paths");
-        };
-        Documentation {
-            title : String::from(title),
-            host : String::from(host),
-            base_url : String::from(""),
-            requests: requests,
-            schemas : schemas
+//the company publishing the documentation, as configured for the project
+#[derive(Serialize, Debug)]
+struct Company {
+    name : String,
+    address : Vec<String>,
+    website : String,
+    logo : String
+}
+impl Company {
+    pub fn new(format : &dyn Format, config : &Config) -> Company {
+        //without a configured logo the one shipped with the LaTeX assets is used
+        let logo = config.logo.as_ref().map(|logo| logo.to_string_lossy().into_owned()).unwrap_or_else(|| String::from("ubique-logo.png"));
+        Company {
+            name : format.text(&config.company),
+            address : config.address.iter().map(|line| format.text(line)).collect(),
+            website : format.url(&config.website),
+            logo : format.url(&logo)
         }
     }
 }
 
 #[derive(Serialize, Debug)]
 struct Request {
+    //location in the specification, written into the LaTeX document to trace errors back
+    pointer : String,
     title : String,
-    methods : Vec<Method>,
+    summary : String,
     description : String,
-    request_headers: Option<RequestHeader>,
-    query_parameters: Option<QueryParameter>,
-    response_body: Option<ResponseBody>,
-    responses : Vec<Response>
+    //servers overriding the ones of the document for this path
+    servers : Vec<Server>,
+    has_servers : bool,
+    methods : Vec<Method>
 }
 impl Request {
-    pub fn new(title : String, methods : Vec<Method>, description : String, request_headers : Option<RequestHeader>, query_parameters : Option<QueryParameter>, response_body : Option<ResponseBody>, responses : Vec<Response>) -> Request {
+    pub fn new(format : &dyn Format, title : String, summary : String, description : String, servers : Vec<Server>, methods : Vec<Method>) -> Request {
         Request {
-            title,
-            methods,
+            pointer : pointer(&["paths", &title]),
+            title : format.text(&title),
+            summary : format.text(&summary),
             description,
-            request_headers,
-            query_parameters,
-            response_body,
-            responses
+            has_servers : !servers.is_empty(),
+            servers,
+            methods
+        }
+    }
+}
+#[derive(Serialize, Debug)]
+struct Tag {
+    name : String,
+    label : String,
+    description : String,
+    external_docs : Option<ExternalDocs>,
+    requests : Vec<Request>
+}
+impl Tag {
+    pub fn new(format : &dyn Format, name : String, description : String, external_docs : Option<ExternalDocs>) -> Tag {
+        Tag {
+            label : label(&name),
+            name : format.text(&name),
+            description,
+            external_docs,
+            requests : Vec::new()
+        }
+    }
+    //add the method to the entry of its path, creating the entry when needed
+    pub fn add(&mut self, request : &Request, method : Method) {
+        match self.requests.iter_mut().find(|existing| existing.title == request.title) {
+            Some(existing) => existing.methods.push(method),
+            None => self.requests.push(Request {
+                pointer : request.pointer.clone(),
+                title : request.title.clone(),
+                summary : request.summary.clone(),
+                description : request.description.clone(),
+                servers : request.servers.clone(),
+                has_servers : request.has_servers,
+                methods : vec![method]
+            })
         }
     }
 }
 #[derive(Serialize, Debug)]
+struct ExternalDocs {
+    url : String,
+    description : String
+}
+impl ExternalDocs {
+    pub fn new(format : &dyn Format, url : String, description : String) -> ExternalDocs {
+        ExternalDocs {
+            url : format.url(&url),
+            description : format.text(&description)
+        }
+    }
+}
+#[derive(Serialize, Debug, Clone)]
 struct Method {
+    pointer : String,
     method : String,
     path : String,
     summary : String,
-    description : String
+    description : String,
+    title : String,
+    tags : Vec<String>,
+    //the operationId, links of responses refer to the operation by it
+    operation_id : String,
+    operation_label : String,
+    //servers overriding the ones of the path and the document for this operation
+    servers : Vec<Server>,
+    has_servers : bool,
+    security : Vec<SecurityRequirement>,
+    has_security : bool,
+    //the operation explicitly does not need any authentication
+    is_public : bool,
+    path_parameters: Option<PathParameter>,
+    request_headers: Option<RequestHeader>,
+    query_parameters: Option<QueryParameter>,
+    cookie_parameters: Option<CookieParameter>,
+    request_body: Option<RequestBody>,
+    responses : Vec<Response>
 }
 impl Method {
-    pub fn new(method : String, path:String, summary : String, description : String) -> Method{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(format : &dyn Format, method : String, path:String, summary : String, description : String, tags : Vec<String>, servers : Vec<Server>, path_parameters : Option<PathParameter>, request_headers : Option<RequestHeader>, query_parameters : Option<QueryParameter>, cookie_parameters : Option<CookieParameter>, request_body : Option<RequestBody>, responses : Vec<Response>) -> Method{
+        let pointer = pointer(&["paths", &path, &method]);
+        let method = method.to_uppercase();
+        let path = format.text(&path);
+        let summary = format.text(&summary);
+        //operations are titled by their summary, if they have one
+        let title = if summary.is_empty() {
+            format!("{} {}", method, path)
+        } else {
+            summary.clone()
+        };
         Method {
+            pointer,
             method,
             path,
             summary,
-            description
+            description,
+            title,
+            tags,
+            operation_id : String::new(),
+            operation_label : String::new(),
+            has_servers : !servers.is_empty(),
+            servers,
+            security : Vec::new(),
+            has_security : false,
+            is_public : false,
+            path_parameters,
+            request_headers,
+            query_parameters,
+            cookie_parameters,
+            request_body,
+            responses
+        }
+    }
+    pub fn set_operation_id(&mut self, format : &dyn Format, operation_id : String) {
+        self.operation_label = label(&operation_id);
+        self.operation_id = format.text(&operation_id);
+    }
+    pub fn set_security(&mut self, security : Vec<SecurityRequirement>, is_public : bool) {
+        self.has_security = !security.is_empty();
+        self.security = security;
+        self.is_public = is_public;
+    }
+    fn fields_mut(&mut self) -> Vec<&mut Field> {
+        let mut fields : Vec<&mut Field> = Vec::new();
+        if let Some(ref mut path_parameters) = self.path_parameters {
+            fields.extend(path_parameters.params.iter_mut());
         }
+        if let Some(ref mut request_headers) = self.request_headers {
+            fields.extend(request_headers.headers.iter_mut());
+        }
+        if let Some(ref mut query_parameters) = self.query_parameters {
+            fields.extend(query_parameters.params.iter_mut());
+        }
+        if let Some(ref mut cookie_parameters) = self.cookie_parameters {
+            fields.extend(cookie_parameters.params.iter_mut());
+        }
+        if let Some(ref mut request_body) = self.request_body {
+            for media_type in request_body.content.iter_mut() {
+                fields.extend(media_type.schema.as_mut());
+                fields.extend(media_type.parts.iter_mut().map(|part| &mut part.field));
+            }
+        }
+        for response in self.responses.iter_mut() {
+            fields.extend(response.content.iter_mut().filter_map(|media_type| media_type.schema.as_mut()));
+            fields.extend(response.headers.iter_mut());
+        }
+        fields
     }
 }
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 struct RequestHeader {
     headers : Vec<Field>
 }
@@ -436,49 +1391,176 @@ impl RequestHeader {
         self.headers.push(field);
     }
      pub fn required(&self) -> bool {
-        self.headers.len()> 0
+        !self.headers.is_empty()
     }
 }
-#[derive(Serialize, Debug)]
-struct ResponseBody{
-    params : Vec<Field>
+#[derive(Serialize, Debug, Clone)]
+struct RequestBody{
+    description : String,
+    required : bool,
+    content : Vec<MediaType>
 }
-impl ResponseBody {
-    pub fn new() -> ResponseBody {
-        ResponseBody {
-            params : Vec::new()
+impl RequestBody {
+    pub fn new(description : String, required : bool, content : Vec<MediaType>) -> RequestBody {
+        RequestBody {
+            description,
+            required,
+            content
         }
     }
-    pub fn add(&mut self, field : Field) {
-        self.params.push(field);
+}
+//the payload of a request body or a response in one of its media types
+#[derive(Serialize, Debug, Clone)]
+struct MediaType {
+    media_type : String,
+    schema : Option<Field>,
+    //the fields of a form body
+    parts : Vec<FormPart>,
+    has_parts : bool,
+    examples : Vec<Example>,
+    has_examples : bool,
+    encoding : Vec<Encoding>,
+    has_encoding : bool
+}
+impl MediaType {
+    pub fn new(format : &dyn Format, media_type : String, schema : Option<Field>, examples : Vec<Example>, encoding : Vec<Encoding>) -> MediaType {
+        MediaType {
+            media_type : format.text(&media_type),
+            schema,
+            parts : Vec::new(),
+            has_parts : false,
+            has_examples : !examples.is_empty(),
+            examples,
+            has_encoding : !encoding.is_empty(),
+            encoding
+        }
     }
-    pub fn required(&self) -> bool {
-        self.params.len()> 0
+    pub fn set_parts(&mut self, parts : Vec<FormPart>) {
+        self.has_parts = !parts.is_empty();
+        self.parts = parts;
     }
 }
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
+struct FormPart {
+    field : Field,
+    encoding : Option<Encoding>
+}
+impl FormPart {
+    pub fn new(field : Field, encoding : Option<Encoding>) -> FormPart {
+        FormPart {
+            field,
+            encoding
+        }
+    }
+}
+#[derive(Serialize, Debug, Clone)]
+struct Example {
+    name : String,
+    summary : String,
+    description : String,
+    //the example as a code block of the output format
+    value : String,
+    external_value : String,
+    //made up from the schema, the specification has no example
+    generated : bool
+}
+impl Example {
+    pub fn new(format : &dyn Format, name : String, summary : String, description : String, value : String, external_value : String) -> Example {
+        Example {
+            name : format.text(&name),
+            summary : format.text(&summary),
+            description,
+            value : if value.is_empty() { value } else { format.markdown_item(&code_block(&value)) },
+            external_value : format.url(&external_value),
+            generated : false
+        }
+    }
+    pub fn set_generated(&mut self, generated : bool) {
+        self.generated = generated;
+    }
+}
+//a fenced code block, the fence is longer than any backticks in the code
+fn code_block(code : &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for character in code.chars() {
+        current = if character == '`' { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}\n{}\n{}\n", fence, code, fence)
+}
+//how a property of a multipart or form body is sent
+#[derive(Serialize, Debug, Clone)]
+struct Encoding {
+    property : String,
+    content_type : String,
+    //headers sent with the part of a multipart body
+    headers : Vec<Field>,
+    has_headers : bool,
+    //serialization of urlencoded values
+    style : String,
+    explode : String,
+    allow_reserved : bool
+}
+impl Encoding {
+    pub fn new(format : &dyn Format, property : String, content_type : String, headers : Vec<Field>, style : String, explode : String, allow_reserved : bool) -> Encoding {
+        Encoding {
+            property : format.text(&property),
+            content_type : format.text(&content_type),
+            has_headers : !headers.is_empty(),
+            headers,
+            style : format.text(&style),
+            explode,
+            allow_reserved
+        }
+    }
+}
+#[derive(Serialize, Debug, Clone)]
 struct Field{
     field : String,
     param_type : String,
     required : bool,
     description: String,
     example : String,
-    pure_type : String
+    pure_type : String,
+    //whether pure_type is documented as a model which can be linked
+    is_model : bool,
+    inherited_from : Option<Link>
 }
 impl Field {
-    pub fn new(field : String, param_type : String, required : bool, description: String, example : String) -> Field {
-        let pure_type = param_type.replace("[", "").replace("]","").replace("Map<string,", "").replace(">", "");
+    pub fn new(format : &dyn Format, field : String, param_type : String, required : bool, description: String, example : String) -> Field {
+        let pure_type = label(&param_type.replace(['[', ']'], "").replace("Map<string,", "").replace('>', ""));
         Field {
-            field,
-            param_type ,
+            field : format.text(&field),
+            param_type : format.text(&param_type),
             required ,
             description,
-            example,
-            pure_type
+            example : format.text(&example),
+            pure_type,
+            is_model : false,
+            inherited_from : None
         }
     }
+    pub fn set_inherited_from(&mut self, format : &dyn Format, inherited_from : Option<String>) {
+        self.inherited_from = inherited_from.map(|name| Link::new(format, &name));
+    }
 }
-#[derive(Serialize, Debug)]
+//a reference to a model, shown by its name and linked by its label
+#[derive(Serialize, Debug, Clone)]
+struct Link {
+    name : String,
+    label : String
+}
+impl Link {
+    pub fn new(format : &dyn Format, name : &str) -> Link {
+        Link {
+            name : format.text(name),
+            label : label(name)
+        }
+    }
+}
+#[derive(Serialize, Debug, Clone)]
 struct QueryParameter {
     params : Vec<Field>
 }
@@ -492,59 +1574,141 @@ impl QueryParameter {
         self.params.push(field);
     }
      pub fn required(&self) -> bool {
-        self.params.len()> 0
+        !self.params.is_empty()
+    }
+}
+#[derive(Serialize, Debug, Clone)]
+struct PathParameter {
+    params : Vec<Field>
+}
+impl PathParameter {
+    pub fn new() -> PathParameter {
+        PathParameter {
+            params : Vec::new()
+        }
+    }
+    pub fn add(&mut self, field : Field) {
+        self.params.push(field);
+    }
+     pub fn required(&self) -> bool {
+        !self.params.is_empty()
+    }
+}
+#[derive(Serialize, Debug, Clone)]
+struct CookieParameter {
+    params : Vec<Field>
+}
+impl CookieParameter {
+    pub fn new() -> CookieParameter {
+        CookieParameter {
+            params : Vec::new()
+        }
+    }
+    pub fn add(&mut self, field : Field) {
+        self.params.push(field);
+    }
+     pub fn required(&self) -> bool {
+        !self.params.is_empty()
     }
 }
 #[derive(Serialize, Debug)]
 struct Schema {
+    pointer : String,
     name : String,
+    label : String,
     fields : Vec<Field>,
-    enumFields : Vec<Field>,
-    is_enum : bool
+    //templates refer to the values as enumFields
+    #[serde(rename = "enumFields")]
+    enum_fields : Vec<Field>,
+    is_enum : bool,
+    has_fields : bool,
+    composition : Option<Composition>
 }
 impl Schema {
-    pub fn new (name : String, fields : Vec<Field>, enumFields : Vec<Field>) -> Schema {
-        let is_enum = enumFields.len() > 0;
+    pub fn new (format : &dyn Format, name : String, fields : Vec<Field>, enum_fields : Vec<Field>, composition : Option<Composition>) -> Schema {
+        let is_enum = !enum_fields.is_empty();
+        let has_fields = !fields.is_empty();
         Schema {
-            name,
+            pointer : String::new(),
+            label : label(&name),
+            name : format.text(&name),
             fields,
-            enumFields,
-            is_enum
+            enum_fields,
+            is_enum,
+            has_fields,
+            composition
+        }
+    }
+    pub fn set_pointer(&mut self, pointer : String) {
+        self.pointer = pointer;
+    }
+    fn fields_mut(&mut self) -> Vec<&mut Field> {
+        let mut fields : Vec<&mut Field> = self.fields.iter_mut().collect();
+        if let Some(ref mut composition) = self.composition {
+            fields.extend(composition.parents.iter_mut());
+            fields.extend(composition.one_of.iter_mut());
+            fields.extend(composition.any_of.iter_mut());
+            if let Some(ref mut discriminator) = composition.discriminator {
+                fields.extend(discriminator.mapping.iter_mut());
+            }
         }
+        fields
     }
 }
-
-#[derive(Clone)]
-pub enum HttpStatus {
-    Status(&'static str,&'static str)
+#[derive(Serialize, Debug)]
+struct Composition {
+    parents : Vec<Field>,
+    one_of : Vec<Field>,
+    any_of : Vec<Field>,
+    discriminator : Option<Discriminator>,
+    is_inherited : bool,
+    is_one_of : bool,
+    is_any_of : bool
 }
-pub fn get_status_string_from_code(status_code : String) ->  &'static HttpStatus{
-    match status_code.as_str() {
-        "200" => {
-            &HttpOk
-        },
-        "400" => {
-            &HttpBadRequest
-        }, 
-        "403" => {
-            &HttpForbidden
-        },
-        _ => {
-            &HttpInternalServerError   
+impl Composition {
+    pub fn new(parents : Vec<Field>, one_of : Vec<Field>, any_of : Vec<Field>, discriminator : Option<Discriminator>) -> Composition {
+        let is_inherited = !parents.is_empty();
+        let is_one_of = !one_of.is_empty();
+        let is_any_of = !any_of.is_empty();
+        Composition {
+            parents,
+            one_of,
+            any_of,
+            discriminator,
+            is_inherited,
+            is_one_of,
+            is_any_of
         }
     }
 }
-
-static HttpOk : HttpStatus = HttpStatus::Status("200", "Success");
-static HttpForbidden : HttpStatus = HttpStatus::Status("403", "Forbidden");
-static HttpBadRequest : HttpStatus = HttpStatus::Status("400", "Bad Request");
-static HttpInternalServerError : HttpStatus = HttpStatus::Status("500", "Internal Server Error");
-
 #[derive(Serialize, Debug)]
+struct Discriminator {
+    property_name : String,
+    mapping : Vec<Field>,
+    has_mapping : bool
+}
+impl Discriminator {
+    pub fn new(format : &dyn Format, property_name : String, mapping : Vec<Field>) -> Discriminator {
+        let has_mapping = !mapping.is_empty();
+        Discriminator {
+            property_name : format.text(&property_name),
+            mapping,
+            has_mapping
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
 struct Response {
-    params : Vec<Field>,
-    application_json : bool,
-    content_type : String,
+    pointer : String,
+    content : Vec<MediaType>,
+    has_content : bool,
+    headers : Vec<Field>,
+    has_headers : bool,
+    links : Vec<ResponseLink>,
+    has_links : bool,
+    //set when the response returns the error model shared by its status
+    shared_error : Option<SharedError>,
     description : String,
     status_code : String,
     status_string : String,
@@ -552,117 +1716,116 @@ struct Response {
 }
 impl Response {
      pub fn new() -> Response {
-         let HttpStatus::Status(status_code,status_string) = HttpOk;
+        let status = HttpStatus::parse("200");
         Response {
-            status_code : status_code.to_string(),
-            status_string: status_string.to_string(),
-            params : Vec::new(),
-            application_json : true,
-            content_type : String::from("application/json"),
+            pointer : String::new(),
+            status_code : status.code,
+            status_string: String::from(status.reason),
+            content : Vec::new(),
+            has_content : false,
+            headers : Vec::new(),
+            has_headers : false,
+            links : Vec::new(),
+            has_links : false,
+            shared_error : None,
             description : String::from(""),
             error : false
         }
     }
-    pub fn add(&mut self, field : Field) {
-        self.params.push(field);
+    pub fn set_content(&mut self, content : Vec<MediaType>) {
+        self.has_content = !content.is_empty();
+        self.content = content;
     }
-     pub fn required(&self) -> bool {
-        self.params.len()> 0
+    pub fn set_headers(&mut self, headers : Vec<Field>) {
+        self.has_headers = !headers.is_empty();
+        self.headers = headers;
     }
-    pub fn set_content_type(&mut self, content_type : String) {
-        if content_type.contains("application/json") {
-            self.application_json = true;
-        } else {
-            self.application_json = false;
+    pub fn set_links(&mut self, links : Vec<ResponseLink>) {
+        self.has_links = !links.is_empty();
+        self.links = links;
+    }
+    //responses which return nothing but the shared model refer to it instead of repeating it
+    pub fn set_shared_error(&mut self, error : &SharedError) {
+        let returns_model = |media_type : &MediaType| media_type.schema.as_ref().map(|schema| schema.pure_type == error.model.label).unwrap_or(false);
+        if self.has_content && self.content.iter().all(returns_model) {
+            self.shared_error = Some(error.clone());
         }
-        self.content_type = content_type;
+    }
+    pub fn set_pointer(&mut self, pointer : String) {
+        self.pointer = pointer;
     }
     pub fn set_description(&mut self,desc: String) {
         self.description = desc;
     }
-    pub fn set_status_code(&mut self, code : String) {
-        let HttpStatus::Status(status_code, status_string) = get_status_string_from_code(code);
-        self.status_code = String::from(*status_code);
-        self.status_string = String::from(*status_string);
-        match self.status_code.as_str() {
-            "200" | "302" => {
-                self.error = false;
-            },
-            _ => {
-                self.error = true;
-            }
+    pub fn set_status_code(&mut self, format : &dyn Format, code : String) {
+        let status = HttpStatus::parse(&code);
+        self.error = status.is_error();
+        self.status_code = format.text(&status.code);
+        self.status_string = format.text(status.reason);
+    }
+}
+//the model returned by all error responses of a status code or range
+#[derive(Serialize, Debug, Clone)]
+struct SharedError {
+    status_code : String,
+    status_string : String,
+    is_default : bool,
+    model : Link
+}
+impl SharedError {
+    pub fn new(format : &dyn Format, status : HttpStatus, model : &str) -> SharedError {
+        SharedError {
+            is_default : status.code == "default",
+            status_code : format.text(&status.code),
+            status_string : format.text(status.reason),
+            model : Link::new(format, model)
         }
     }
 }
-
-//line matches
-static LIST_MARKDOWN : &'static str = r" *[-] (?P<item>.*)";
-
-//inner matches
-static EMPH_STAR : &'static str = "\\*(?P<content>.*?)\\*";
-static EMPH_UNDERLINE : &'static str = "_(?P<content>.*?)_";
-static STRONG_STAR : &'static str = "\\*\\*(?P<content>.*?)\\*\\*";
-static STRONG_UNDERLINE : &'static str = "__(?P<content>.*?)__";
-static LINK : &'static str = "\\[(?P<displayText>.*)\\]\\((?P<link>https?://[A-z0-9.-_\\\\/]*)( \"(?P<hover>.*)\")?\\)";
-pub fn markdown_to_latex(markdown : &str) -> String {
-    let mut newString = String::new();
-    let mut inList = false;
-    //they can only be matched once in a line
-    let line_level_regex = RegexSet::new(&vec![LIST_MARKDOWN]).unwrap();
-
-    let list_regex = regex::Regex::new(LIST_MARKDOWN).unwrap();
-    for line in markdown.lines() {
-        //ideally only one line level match occurs so take the first match
-        if line_level_regex.is_match(line) {
-            let lineLevelMatches = line_level_regex.matches(line).into_iter().next();
-            match lineLevelMatches {
-                Some(0) => {
-                    //we have a list 
-                    if !inList {
-                        inList = true;
-                        newString.push_str("\\begin{itemize}\n");
-                    }
-                    let result = list_regex.replace_all(line, "\\item $item");
-                    
-                    newString.push_str(&inner_replace(&result));
-                },
-                _ => {}
-            }
-        } else {
-            //normal line replace all modifier
-            if inList {
-                inList = false;
-                newString.push_str("\\end{itemize}\n");
-            }
-            newString.push_str(&inner_replace(&line));
+//a link from a response to the operation which can be called with its values
+#[derive(Serialize, Debug, Clone)]
+struct ResponseLink {
+    name : String,
+    operation_id : String,
+    has_operation_id : bool,
+    operation_label : String,
+    //a reference to the operation, for operations without an operationId
+    operation_ref : String,
+    description : String,
+    parameters : Vec<LinkParameter>,
+    has_parameters : bool,
+    request_body : String
+}
+impl ResponseLink {
+    pub fn new(format : &dyn Format, name : String, operation_id : String, operation_ref : String, description : String, parameters : Vec<LinkParameter>) -> ResponseLink {
+        ResponseLink {
+            name : format.text(&name),
+            operation_label : label(&operation_id),
+            has_operation_id : !operation_id.is_empty(),
+            operation_id : format.text(&operation_id),
+            operation_ref : format.text(&operation_ref),
+            description,
+            has_parameters : !parameters.is_empty(),
+            parameters,
+            request_body : String::new()
         }
-        newString.push('\n');
     }
-    //if the text ends with a list we must ensure that we close the itemize environment
-    if inList {
-        inList = false;
-        newString.push_str(r"\end{itemize}");
+    pub fn set_request_body(&mut self, format : &dyn Format, request_body : String) {
+        self.request_body = format.text(&request_body);
     }
-    newString
 }
-
-
-fn inner_replace(string : &str) -> String {
-    let modifier_regex = RegexSet::new(&vec![STRONG_STAR,STRONG_UNDERLINE,EMPH_STAR, EMPH_UNDERLINE,LINK]).unwrap();
-
-    let all_matches = vec![regex::Regex::new(STRONG_STAR).unwrap(),regex::Regex::new(STRONG_UNDERLINE).unwrap(),regex::Regex::new(EMPH_STAR).unwrap(),regex::Regex::new(EMPH_UNDERLINE).unwrap(),regex::Regex::new(LINK).unwrap()];
-    let replacements = vec![r"\textbf{$content}",r"\textbf{$content}", r"\emph{$content}",r"\emph{$content}",r"\url[$displayText]{$link}"];
-    if !modifier_regex.is_match(string) {
-       String::from(string)
-    }
-    else {
-        let mut new_string = String::from(string);
-        while modifier_regex.is_match(&new_string) {
-            let first_match = modifier_regex.matches(&new_string).into_iter().next().unwrap();
-            let result = all_matches[first_match].replace_all(&new_string, replacements[first_match]);
-            new_string = String::from(result);
+//a parameter of the linked operation and the runtime expression giving its value
+#[derive(Serialize, Debug, Clone)]
+struct LinkParameter {
+    name : String,
+    expression : String
+}
+impl LinkParameter {
+    pub fn new(format : &dyn Format, name : String, expression : String) -> LinkParameter {
+        LinkParameter {
+            name : format.text(&name),
+            expression : format.text(&expression)
         }
-        inner_replace(new_string.as_str())
     }
 }
 
@@ -671,53 +1834,182 @@ mod tests {
     use super::*;
 
     #[test]
-    pub fn test_markdown_list () {
-        let string = "- test\n- item2\n- a b c";
-        assert_eq!(markdown_to_latex(string), "\\begin{itemize}\n\\item test\n\\item item2\n\\item a b c\n\\end{itemize}");
+    pub fn test_all_of_inheritance() {
+        let doc = yaml_rust::YamlLoader::load_from_str("components:\n  schemas:\n    Pet:\n      required: [name]\n      properties:\n        name: {type: string}\n    Dog:\n      allOf:\n        - $ref: '#/components/schemas/Pet'\n        - required: [bark]\n          properties:\n            bark: {type: boolean}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let schema = get_schema(&resolver, &Latex, String::from("Dog"), &doc["components"]["schemas"]["Dog"], &mut Vec::new());
+        assert_eq!(schema.fields.len(), 2);
+        assert!(schema.fields.iter().all(|field| field.required));
+        assert_eq!(schema.fields[0].inherited_from.as_ref().map(|parent| parent.label.as_str()), Some("Pet"));
+        assert!(schema.fields[1].inherited_from.is_none());
+        assert_eq!(schema.composition.unwrap().parents[0].param_type, "Pet");
+    }
+
+    #[test]
+    pub fn test_non_string_keys() {
+        let doc = yaml_rust::YamlLoader::load_from_str("openapi: 3.0.0\ninfo: {title: Keys, version: '1'}\npaths: {}\ncomponents:\n  schemas:\n    404:\n      required: [404]\n      properties:\n        404: {type: string}\n        true: {type: boolean}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let schema = get_schema(&resolver, &Latex, String::from("Status"), &doc["components"]["schemas"][404], &mut Vec::new());
+        assert_eq!(schema.fields.iter().map(|field| (field.field.as_str(), field.required)).collect::<Vec<(&str, bool)>>(), vec![("404", true), ("true", false)]);
+        let documentation = Documentation::new(&doc, &Latex, &Config::new());
+        assert_eq!(documentation.schemas[0].name, "404");
+    }
+
+    #[test]
+    pub fn test_path_item_parameters() {
+        let doc = yaml_rust::YamlLoader::load_from_str("parameters:\n  - {name: id, in: path, description: shared}\n  - {name: id, in: query}\nget:\n  parameters:\n    - {name: id, in: path, description: own}\n    - {name: limit, in: query}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let parameters = get_parameters(&resolver, &doc, &doc["get"]);
+        let described : Vec<(&str, &str)> = parameters.iter().map(|p| (p["in"].as_str().unwrap(), p["description"].as_str().unwrap_or(""))).collect();
+        assert_eq!(described, vec![("path", "own"), ("query", ""), ("query", "")]);
+        assert_eq!(parameters[2]["name"].as_str(), Some("limit"));
+    }
+
+    #[test]
+    pub fn test_nested_inline_objects() {
+        let doc = yaml_rust::YamlLoader::load_from_str("properties:\n  shippingAddress:\n    type: object\n    properties:\n      lines:\n        type: array\n        items:\n          properties:\n            text: {type: string}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let mut nested = Vec::new();
+        let schema = get_schema(&resolver, &Latex, String::from("Order"), &doc, &mut nested);
+        assert_eq!(schema.fields[0].param_type, "Order.shippingAddress");
+        assert_eq!(nested.iter().map(|schema| schema.name.as_str()).collect::<Vec<&str>>(), vec!["Order.shippingAddress", "Order.shippingAddress.lines"]);
+        assert_eq!(nested[0].fields[0].param_type, "Order.shippingAddress.lines[]");
+        assert_eq!(nested[0].fields[0].pure_type, "Order.shippingAddress.lines");
+    }
+
+    #[test]
+    pub fn test_inherited_inline_objects() {
+        let doc = yaml_rust::YamlLoader::load_from_str("components:\n  schemas:\n    Pet:\n      properties:\n        address:\n          properties:\n            street: {type: string}\n    Dog:\n      allOf:\n        - $ref: '#/components/schemas/Pet'\n        - properties:\n            collar:\n              properties:\n                color: {type: string}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let mut nested = Vec::new();
+        let schema = get_schema(&resolver, &Latex, String::from("Dog"), &doc["components"]["schemas"]["Dog"], &mut nested);
+        assert_eq!(schema.fields[0].param_type, "Pet.address");
+        assert_eq!(schema.fields[1].param_type, "Dog.collar");
+        assert_eq!(nested.iter().map(|schema| schema.name.as_str()).collect::<Vec<&str>>(), vec!["Dog.collar"]);
+    }
+
+    #[test]
+    pub fn test_license_without_url() {
+        let doc = yaml_rust::YamlLoader::load_from_str("openapi: 3.0.0\ninfo:\n  title: Pets\n  version: '1'\n  license: {name: MIT}\npaths: {}\n").unwrap().remove(0);
+        let config = Config::new();
+        let latex = render_string(&Documentation::new(&doc, &Latex, &config), &Templates::new(&templates::LATEX_TEMPLATES, None), "documentation.tex.mustache").unwrap();
+        assert!(latex.contains("provided under the MIT license"));
+        let markdown = render_string(&Documentation::new(&doc, &Markdown, &config), &Templates::new(&templates::MARKDOWN_TEMPLATES, None), "index.md.mustache").unwrap();
+        assert!(markdown.contains("## License\n\nMIT\n"));
+        let html = render_string(&Documentation::new(&doc, &Html, &config), &Templates::new(&templates::HTML_TEMPLATES, None), "documentation.html.mustache").unwrap();
+        assert!(html.contains("<h2>License</h2>\n<p>MIT</p>"));
+    }
+
+    #[test]
+    pub fn test_self_referencing_pointer() {
+        let doc = yaml_rust::YamlLoader::load_from_str("openapi: 3.0.0\ninfo: {title: Trees, version: '1'}\npaths: {}\ncomponents:\n  schemas:\n    Tree:\n      properties:\n        children:\n          type: array\n          items: {$ref: '#/components/schemas/Tree/properties/children'}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        assert_eq!(get_type_name_without_schema(&resolver, &doc["components"]["schemas"]["Tree"]["properties"]["children"]).unwrap(), "unknown property[][]");
+        let documentation = Documentation::new(&doc, &Latex, &Config::new());
+        assert!(render_string(&documentation, &Templates::new(&templates::LATEX_TEMPLATES, None), "documentation.tex.mustache").is_ok());
     }
+
+    #[test]
+    pub fn test_contact_email() {
+        let contact = Contact::new(&Latex, String::new(), String::new(), String::from("team_x@example.com"));
+        assert_eq!((contact.email.as_str(), contact.mailto.as_str()), ("team\\_x@example.com", "mailto:team_x@example.com"));
+    }
+
+    #[test]
+    pub fn test_servers() {
+        let doc = yaml_rust::YamlLoader::load_from_str("- url: 'https://{region}.example.com/v1'\n  variables:\n    region:\n      default: eu\n      enum: [eu, us]\n- url: /local\n").unwrap().remove(0);
+        let servers = get_servers(&Latex, &doc);
+        assert_eq!(servers[0].url, "https://\\{region\\}.example.com/v1");
+        assert_eq!(servers[0].variables[0].values, "eu, us");
+        assert!(!servers[1].has_variables);
+    }
+
+    #[test]
+    pub fn test_security() {
+        let doc = yaml_rust::YamlLoader::load_from_str("security:\n  - api_key: []\npaths:\n  /a:\n    get:\n      security:\n        - oauth: [read, write]\n          api_key: []\n        - {}\n    post:\n      security: []\n    put: {}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let requests = get_paths(&resolver, &Latex, &doc["paths"], &doc["security"]);
+        let methods = &requests[0].methods;
+        assert_eq!(methods[0].security[0].schemes.iter().map(|scheme| scheme.name.as_str()).collect::<Vec<&str>>(), vec!["oauth", "api\\_key"]);
+        assert_eq!(methods[0].security[0].schemes[0].scopes, "read, write");
+        assert!(methods[0].security[0].schemes[1].combined);
+        assert!(methods[0].security[1].is_anonymous && methods[0].security[1].alternative);
+        assert!(methods[1].is_public && !methods[1].has_security);
+        assert!(!methods[2].is_public && methods[2].security[0].schemes[0].label == "api_key");
+    }
+
+    #[test]
+    pub fn test_media_types() {
+        let doc = yaml_rust::YamlLoader::load_from_str("application/json:\n  schema: {$ref: '#/components/schemas/Pet'}\n  example: plain\ntext/csv:\n  schema: {type: string}\n  examples:\n    small: {summary: Two rows, value: 'a,b'}\nmultipart/form-data:\n  encoding:\n    file: {contentType: image/png}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let content = get_content(&resolver, &Latex, &doc);
+        assert_eq!(content.iter().map(|media_type| media_type.media_type.as_str()).collect::<Vec<&str>>(), vec!["application/json", "text/csv", "multipart/form-data"]);
+        assert_eq!(content[0].schema.as_ref().unwrap().param_type, "Pet");
+        assert!(content[0].examples[0].value.contains("plain"));
+        assert_eq!(content[1].examples[0].name, "small");
+        assert!(content[2].schema.is_none() && content[2].has_encoding);
+    }
+
     #[test]
-    pub fn test_markdown_modifiers() {
-        let emph = "*emph*";
-        assert_eq!(markdown_to_latex(emph).trim(),"\\emph{emph}");
-        let emph = "_emph_";
-        assert_eq!(markdown_to_latex(emph).trim(),"\\emph{emph}");
-
-        let strong = "**strong**";
-        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{strong}");
-        let strong = "__strong__";
-        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{strong}");
-         let strong = "***strongemph***";
-        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
-        let strong = "___strongemph___";
-        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
-          let strong = "**_strongemph_**";
-        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
-        let strong = "__*strongemph*__";
-        assert_eq!(markdown_to_latex(strong).trim(),"\\textbf{\\emph{strongemph}}");
-
-        let url = "[test](https://www.google.ch \"Hallo Velo\")";
-        assert_eq!(markdown_to_latex(url).trim(), "\\url[test]{https://www.google.ch}");
-          let url = "[test](https://www.google.ch)";
-        assert_eq!(markdown_to_latex(url).trim(), "\\url[test]{https://www.google.ch}");
+    pub fn test_examples() {
+        let doc = yaml_rust::YamlLoader::load_from_str("application/json:\n  schema:\n    properties:\n      id: {type: integer, example: 7}\n      state: {type: string, enum: [open, closed]}\napplication/problem+json:\n  examples:\n    remote: {externalValue: 'https://example.com/problem.json'}\n    inline: {value: {title: Not found, status: 404}}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let content = get_content(&resolver, &Markdown, &doc);
+        assert!(content[0].examples[0].generated);
+        assert_eq!(content[0].examples[0].value, "```\n  {\n    \"id\": 7,\n    \"state\": \"open\"\n  }\n  ```");
+        assert_eq!((content[1].examples[0].value.as_str(), content[1].examples[0].external_value.as_str()), ("", "https://example.com/problem.json"));
+        assert!(content[1].examples[1].value.contains("\"status\": 404") && !content[1].examples[1].generated);
+        let doc = yaml_rust::YamlLoader::load_from_str("parameters:\n  - {name: ids, in: query, example: [1, 2]}\n  - {name: sort, in: query, examples: {newest: {$ref: '#/components/examples/Newest'}}}\ncomponents:\n  examples:\n    Newest: {summary: Newest first, value: -created}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        assert_eq!(get_example(&resolver, &doc["parameters"][0]), "[1,2]");
+        assert_eq!(get_example(&resolver, &doc["parameters"][1]), "-created");
     }
 
     #[test]
-    pub fn test_line_and_inline() {
-        let all_in_one = "**strong**\n - test1\n- *emph1*\n- [test](https://www.google.ch)\n_emph_";
-        assert_eq!(markdown_to_latex(all_in_one).trim(), "\\textbf{strong}\n\\begin{itemize}\n\\item test1\n\\item \\emph{emph1}\n\\item \\url[test]{https://www.google.ch}\n\\end{itemize}\n\\emph{emph}");
-        let all_in_one = "**strong**\n -test1\n- *emph1*\n- [test](https://www.google.ch)\n_emph_";
-        assert_ne!(markdown_to_latex(all_in_one).trim(), "\\textbf{strong}\n\\begin{itemize}\n\\item test1\n\\item \\emph{emph1}\n\\item \\url[test]{https://www.google.ch}\n\\end{itemize}\n\\emph{emph}");
+    pub fn test_form_parts() {
+        let doc = yaml_rust::YamlLoader::load_from_str("multipart/form-data:\n  schema:\n    required: [file]\n    properties:\n      file: {type: string, format: binary}\n      note: {type: string}\n  encoding:\n    file:\n      contentType: image/png\n      headers:\n        X-Rate: {schema: {type: integer}}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let content = get_content(&resolver, &Latex, &doc);
+        let parts = &content[0].parts;
+        assert_eq!(parts[0].field.param_type, "string (binary)");
+        assert!(parts[0].field.required && !parts[1].field.required);
+        let encoding = parts[0].encoding.as_ref().unwrap();
+        assert_eq!((encoding.content_type.as_str(), encoding.headers[0].param_type.as_str()), ("image/png", "integer"));
+        assert!(parts[1].encoding.is_none() && !content[0].has_encoding);
+    }
 
+    #[test]
+    pub fn test_response_headers_and_links() {
+        let doc = yaml_rust::YamlLoader::load_from_str("/users:\n  post:\n    operationId: createUser\n    responses:\n      '201':\n        description: created\n        headers:\n          Location: {schema: {type: string}}\n        links:\n          GetUser:\n            operationId: getUser\n            parameters: {id: '$response.body#/id'}\n").unwrap().remove(0);
+        let resolver = Resolver::new(&doc);
+        let requests = get_paths(&resolver, &Markdown, &doc, &Yaml::BadValue);
+        let method = &requests[0].methods[0];
+        assert_eq!(method.operation_label, "createUser");
+        let response = &method.responses[0];
+        assert_eq!((response.headers[0].field.as_str(), response.headers[0].param_type.as_str()), ("Location", "string"));
+        assert_eq!(response.links[0].operation_label, "getUser");
+        assert_eq!((response.links[0].parameters[0].name.as_str(), response.links[0].parameters[0].expression.as_str()), ("id", "$response.body#/id"));
     }
+
     #[test]
-    pub fn no_markdown() {
-        let test = "hallo velo. normaler test\n";
-        assert_eq!(markdown_to_latex(test), test);
+    pub fn test_operation_ref_links() {
+        let doc = yaml_rust::YamlLoader::load_from_str("openapi: 3.0.0\ninfo: {title: Users, version: '1'}\npaths:\n  /users:\n    post:\n      responses:\n        '201':\n          description: created\n          links:\n            Next:\n              operationRef: '#/paths/~1users/get'\n").unwrap().remove(0);
+        let config = Config::new();
+        let latex = render_string(&Documentation::new(&doc, &Latex, &config), &Templates::new(&templates::LATEX_TEMPLATES, None), "documentation.tex.mustache").unwrap();
+        assert!(latex.contains("\\ubheader{ Next } & \\#/paths/\\textasciitilde{}1users/get &"));
+        let documentation = Documentation::new(&doc, &Markdown, &config);
+        let markdown = Templates::new(&templates::MARKDOWN_TEMPLATES, None).compile("tag.md.mustache").unwrap().render_to_string(&documentation.tags[0]).unwrap();
+        assert!(markdown.contains("- **Next** to #/paths/~1users/get\n"));
+        let html = render_string(&Documentation::new(&doc, &Html, &config), &Templates::new(&templates::HTML_TEMPLATES, None), "documentation.html.mustache").unwrap();
+        assert!(html.contains("<td><strong>Next</strong></td><td>#/paths/~1users/get</td>"));
     }
 
     #[test]
-    pub fn lazy_match() {
-        let test = "*next* **n**";
-        assert_eq!(markdown_to_latex(test).trim(), "\\emph{next} \\textbf{n}");
+    pub fn test_shared_errors() {
+        let errors = vec![SharedError::new(&Latex, HttpStatus::parse("4xx"), "ErrorResponse"), SharedError::new(&Latex, HttpStatus::parse("404"), "NotFound"), SharedError::new(&Latex, HttpStatus::parse("default"), "Failure")];
+        assert_eq!(find_shared_error(&errors, "404").map(|error| error.model.label.as_str()), Some("NotFound"));
+        assert_eq!(find_shared_error(&errors, "409").map(|error| error.model.label.as_str()), Some("ErrorResponse"));
+        assert_eq!(find_shared_error(&errors, "503").map(|error| error.model.label.as_str()), Some("Failure"));
+        assert!(find_shared_error(&errors, "200").is_none());
     }
 }
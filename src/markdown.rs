@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use crate::format::Format;
+use crate::templates::Templates;
+use crate::Documentation;
+
+//Markdown output, meant to be read in a Git repository next to the code.
+//Descriptions are already Markdown and are kept as they are.
+pub struct Markdown;
+impl Format for Markdown {
+    fn text(&self, text : &str) -> String {
+        escape_markdown(text)
+    }
+    fn markdown(&self, markdown : &str) -> String {
+        String::from(markdown.trim())
+    }
+    //continuation lines are indented to stay part of the item
+    fn markdown_item(&self, markdown : &str) -> String {
+        markdown.trim().lines().collect::<Vec<&str>>().join("\n  ").replace("\n  \n", "\n\n")
+    }
+    fn url(&self, url : &str) -> String {
+        url.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+    }
+}
+
+//escape the characters which would otherwise be read as markup
+pub fn escape_markdown(text : &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]<>|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+//the documentation is written into dir as an index, one file per tag in tags/ and one per model in schemas/
+pub fn write(documentation : &Documentation, templates : &Templates, dir : &Path) -> Result<Vec<PathBuf>, Box<dyn error::Error>> {
+    let index = templates.compile("index.md.mustache")?;
+    let tag = templates.compile("tag.md.mustache")?;
+    let schema = templates.compile("schema.md.mustache")?;
+    let tag_files = file_names(documentation.tags.iter().map(|entry| (entry.name.as_str(), entry.label.as_str())), &dir.join("tags"))?;
+    let schema_files = file_names(documentation.schemas.iter().map(|entry| (entry.name.as_str(), entry.label.as_str())), &dir.join("schemas"))?;
+    let mut written = Vec::new();
+    written.push(write_file(&index, documentation, &dir.join("README.md"))?);
+    for (entry, path) in documentation.tags.iter().zip(tag_files) {
+        written.push(write_file(&tag, entry, &path)?);
+    }
+    for (entry, path) in documentation.schemas.iter().zip(schema_files) {
+        written.push(write_file(&schema, entry, &path)?);
+    }
+    Ok(written)
+}
+
+//files are named after labels, different names like `a b` and `a-b` must not overwrite each other
+fn file_names<'a>(entries : impl Iterator<Item = (&'a str, &'a str)>, dir : &Path) -> Result<Vec<PathBuf>, Box<dyn error::Error>> {
+    let mut names : HashMap<PathBuf, &str> = HashMap::new();
+    let mut paths = Vec::new();
+    for (name, label) in entries {
+        let path = dir.join(format!("{}.md", label));
+        if let Some(other) = names.insert(path.clone(), name) {
+            return Err(format!("{} and {} would both be written to {}", other, name, path.display()).into());
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn write_file<T : Serialize>(template : &mustache::Template, data : &T, path : &Path) -> Result<PathBuf, Box<dyn error::Error>> {
+    let output = template.render_to_string(data).map_err(|err| format!("could not render {}: {}", path.display(), err))?;
+    let output = collapse_blank_lines(&output);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("could not create {}: {}", parent.display(), err))?;
+    }
+    fs::write(path, output).map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+    Ok(path.to_path_buf())
+}
+
+//empty sections leave blank lines behind, only one is kept outside of code blocks
+fn collapse_blank_lines(markdown : &str) -> String {
+    let mut collapsed = String::with_capacity(markdown.len());
+    let mut in_fence = false;
+    let mut previous_blank = true;
+    for line in markdown.lines() {
+        let blank = line.trim().is_empty();
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if blank && previous_blank && !in_fence {
+            continue;
+        }
+        collapsed.push_str(if blank && !in_fence { "" } else { line });
+        collapsed.push('\n');
+        previous_blank = blank;
+    }
+    collapsed.truncate(collapsed.trim_end().len());
+    collapsed.push('\n');
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_markdown_format() {
+        assert_eq!(Markdown.text("Map<string,Foo_Bar>"), "Map\\<string,Foo\\_Bar\\>");
+        assert_eq!(Markdown.markdown("first\nsecond\n\n- item\n"), "first\nsecond\n\n- item");
+        assert_eq!(Markdown.markdown_item("first\nsecond\n\n- item\n"), "first\n  second\n\n  - item");
+        let names = file_names(vec![("a b", "a-b"), ("a-b", "a-b")].into_iter(), Path::new("tags"));
+        assert_eq!(names.unwrap_err().to_string(), "a b and a-b would both be written to tags/a-b.md");
+        assert_eq!(collapse_blank_lines("\n# Title\n\n\n\ntext\n```\na\n\n\nb\n```\n\n"), "# Title\n\ntext\n```\na\n\n\nb\n```\n");
+    }
+}
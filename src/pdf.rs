@@ -0,0 +1,221 @@
+use std::fs;
+use std::io;
+use std::fmt;
+use std::error;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use regex::Regex;
+
+//Files the LaTeX document needs next to it, compiled into the binary like the templates
+pub static ASSETS : [(&str, &[u8]); 13] = [
+    ("ubarticle.cls", include_bytes!("../ubarticle.cls")),
+    ("ubique-logo.png", include_bytes!("../ubique-logo.png")),
+    ("Lato/OFL.txt", include_bytes!("../Lato/OFL.txt")),
+    ("Lato/Lato-Regular.ttf", include_bytes!("../Lato/Lato-Regular.ttf")),
+    ("Lato/Lato-Italic.ttf", include_bytes!("../Lato/Lato-Italic.ttf")),
+    ("Lato/Lato-Bold.ttf", include_bytes!("../Lato/Lato-Bold.ttf")),
+    ("Lato/Lato-BoldItalic.ttf", include_bytes!("../Lato/Lato-BoldItalic.ttf")),
+    ("Lato/Lato-Light.ttf", include_bytes!("../Lato/Lato-Light.ttf")),
+    ("Lato/Lato-LightItalic.ttf", include_bytes!("../Lato/Lato-LightItalic.ttf")),
+    ("Lato/Lato-Black.ttf", include_bytes!("../Lato/Lato-Black.ttf")),
+    ("Lato/Lato-BlackItalic.ttf", include_bytes!("../Lato/Lato-BlackItalic.ttf")),
+    ("Lato/Lato-Hairline.ttf", include_bytes!("../Lato/Lato-Hairline.ttf")),
+    ("Lato/Lato-HairlineItalic.ttf", include_bytes!("../Lato/Lato-HairlineItalic.ttf"))
+];
+
+//the templates mark the part of the specification each section was generated from with this comment
+static SPEC_MARKER : &str = "% spec: ";
+//files which change as long as the cross references have not settled
+static CROSS_REFERENCE_FILES : [&str; 3] = ["aux", "toc", "out"];
+
+#[derive(Debug)]
+pub enum BuildError {
+    Io(PathBuf, io::Error),
+    Engine(String, io::Error),
+    Latex(PathBuf, Vec<LatexError>)
+}
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::Io(path, err) => write!(f, "Could not access {}: {}", path.display(), err),
+            BuildError::Engine(engine, err) => write!(f, "Could not run {}: {}", engine, err),
+            BuildError::Latex(log, errors) => {
+                write!(f, "LaTeX failed, see {}", log.display())?;
+                for latex_error in errors {
+                    write!(f, "\n  {}", latex_error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+impl error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+//an error from the LaTeX log together with the part of the specification which produced the offending line
+#[derive(Debug, PartialEq)]
+pub struct LatexError {
+    pub file : String,
+    pub line : Option<usize>,
+    pub message : String,
+    pub element : Option<String>
+}
+impl std::fmt::Display for LatexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.file, line, self.message)?,
+            None => write!(f, "{}: {}", self.file, self.message)?
+        }
+        if let Some(ref element) = self.element {
+            write!(f, " (generated from {})", element)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Build {
+    engine : String,
+    dir : PathBuf,
+    max_runs : u32,
+    //further files the document refers to by their file name
+    files : Vec<PathBuf>
+}
+impl Build {
+    pub fn new(engine : String, dir : PathBuf, max_runs : u32) -> Build {
+        Build {
+            engine,
+            dir,
+            max_runs,
+            files : Vec::new()
+        }
+    }
+
+    pub fn add_file(&mut self, path : PathBuf) {
+        self.files.push(path);
+    }
+
+    //write name.tex and its assets into the build directory and typeset it until the cross references settle
+    pub fn run(&self, name : &str, tex : &str) -> Result<PathBuf, BuildError> {
+        for (asset, content) in ASSETS.iter() {
+            self.write(asset, content)?;
+        }
+        for file in self.files.iter() {
+            let content = fs::read(file).map_err(|err| BuildError::Io(file.clone(), err))?;
+            self.write(&file.file_name().unwrap_or_default().to_string_lossy(), &content)?;
+        }
+        let tex_file = format!("{}.tex", name);
+        self.write(&tex_file, tex.as_bytes())?;
+        let log_path = self.dir.join(format!("{}.log", name));
+        //the engine runs in the build directory, so a relative path to it must not be taken relative to that
+        let engine = if self.engine.contains(std::path::MAIN_SEPARATOR) {
+            fs::canonicalize(&self.engine).map_err(|err| BuildError::Engine(self.engine.clone(), err))?
+        } else {
+            PathBuf::from(&self.engine)
+        };
+        let mut previous = None;
+        for _ in 0..self.max_runs {
+            let status = Command::new(&engine)
+                .args(["-interaction=nonstopmode", "-halt-on-error", "-file-line-error", &tex_file])
+                .current_dir(&self.dir)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .status()
+                .map_err(|err| BuildError::Engine(self.engine.clone(), err))?;
+            let log = fs::read(&log_path).map(|log| String::from_utf8_lossy(&log).into_owned()).unwrap_or_default();
+            if !status.success() {
+                return Err(BuildError::Latex(log_path, parse_log(&log, &tex_file, tex)));
+            }
+            let cross_references = self.cross_references(name);
+            if !log.contains("Rerun to get") && previous.as_ref() == Some(&cross_references) {
+                return Ok(self.dir.join(format!("{}.pdf", name)));
+            }
+            previous = Some(cross_references);
+        }
+        eprintln!("Cross references did not settle after {} runs of {}", self.max_runs, self.engine);
+        Ok(self.dir.join(format!("{}.pdf", name)))
+    }
+
+    fn write(&self, name : &str, content : &[u8]) -> Result<(), BuildError> {
+        let path = self.dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| BuildError::Io(parent.to_path_buf(), err))?;
+        }
+        fs::write(&path, content).map_err(|err| BuildError::Io(path, err))
+    }
+
+    fn cross_references(&self, name : &str) -> Vec<Vec<u8>> {
+        CROSS_REFERENCE_FILES.iter()
+            .map(|extension| fs::read(self.dir.join(format!("{}.{}", name, extension))).unwrap_or_default())
+            .collect()
+    }
+}
+
+//collect the errors of a log written with -file-line-error, errors of the main document are traced back to the specification
+pub fn parse_log(log : &str, tex_file : &str, tex : &str) -> Vec<LatexError> {
+    let file_line = Regex::new(r"^(?:\./)?(?P<file>[^\s:]+):(?P<line>\d+): (?P<message>.*)$").unwrap();
+    let line_number = Regex::new(r"^l\.(?P<line>\d+)").unwrap();
+    let mut errors : Vec<LatexError> = Vec::new();
+    for log_line in log.lines() {
+        if let Some(captures) = file_line.captures(log_line) {
+            let line = captures["line"].parse::<usize>().ok();
+            let file = String::from(&captures["file"]);
+            let element = if Path::new(&file).file_name() == Path::new(tex_file).file_name() {
+                line.and_then(|line| spec_element(tex, line))
+            } else {
+                None
+            };
+            errors.push(LatexError {
+                file,
+                line,
+                message : String::from(&captures["message"]),
+                element
+            });
+        } else if let Some(message) = log_line.strip_prefix("! ") {
+            //errors without a location, the line follows in the context of the error
+            errors.push(LatexError {
+                file : String::from(tex_file),
+                line : None,
+                message : String::from(message),
+                element : None
+            });
+        } else if let Some(captures) = line_number.captures(log_line) {
+            if let Some(last) = errors.last_mut() {
+                if last.line.is_none() {
+                    last.line = captures["line"].parse::<usize>().ok();
+                    last.element = last.line.and_then(|line| spec_element(tex, line));
+                }
+            }
+        }
+    }
+    errors
+}
+
+//the closest marker above the line names the part of the specification it was generated from
+fn spec_element(tex : &str, line : usize) -> Option<String> {
+    tex.lines()
+        .take(line)
+        .filter_map(|tex_line| tex_line.trim_start().strip_prefix(SPEC_MARKER))
+        .last()
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_log() {
+        let tex = "\\part{Pets}\n% spec: #/paths/~1pets\n\\section{ /pets }\n% spec: #/paths/~1pets/get\n\\subsection{ List pets }\n\\foo\n";
+        let log = "This is XeTeX\n(./documentation.tex\n./documentation.tex:6: Undefined control sequence.\nl.6 \\foo\n\n./ubarticle.cls:3: LaTeX Error: File `titlesec.sty' not found.\n! Emergency stop.\nl.1 \\part{Pets}\n";
+        let errors = parse_log(log, "documentation.tex", tex);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].to_string(), "documentation.tex:6: Undefined control sequence. (generated from #/paths/~1pets/get)");
+        assert_eq!(errors[1].element, None);
+        assert_eq!(errors[1].file, "ubarticle.cls");
+        assert_eq!(errors[2].line, Some(1));
+        assert_eq!(errors[2].element, None);
+    }
+}
@@ -0,0 +1,164 @@
+use yaml_rust::Yaml;
+use std::fmt;
+use std::error;
+
+//Resolution of `$ref` JSON pointers against the loaded specification tree
+#[derive(Debug, Clone)]
+pub enum ResolveError {
+    NotFound(String),
+    Cycle(String),
+    External(String)
+}
+impl std::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolveError::NotFound(reference) => write!(f, "Could not resolve reference {}", reference),
+            ResolveError::Cycle(reference) => write!(f, "Reference {} is part of a cycle", reference),
+            ResolveError::External(reference) => write!(f, "Reference {} points to an external document", reference)
+        }
+    }
+}
+impl error::Error for ResolveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+pub struct Resolver<'a> {
+    root : &'a Yaml
+}
+impl<'a> Resolver<'a> {
+    pub fn new(root : &'a Yaml) -> Resolver<'a> {
+        Resolver {
+            root
+        }
+    }
+
+    //follow the `$ref` chain starting at node until we reach a node which is not a reference
+    pub fn resolve(&self, node : &'a Yaml) -> Result<&'a Yaml, ResolveError> {
+        let mut current = node;
+        let mut seen : Vec<&str> = Vec::new();
+        while let Yaml::String(reference) = &current["$ref"] {
+            if seen.contains(&reference.as_str()) {
+                return Err(ResolveError::Cycle(reference.clone()));
+            }
+            seen.push(reference.as_str());
+            current = self.lookup(reference)?;
+        }
+        Ok(current)
+    }
+
+    //look up a single local reference of the form `#/json/pointer`
+    pub fn lookup(&self, reference : &str) -> Result<&'a Yaml, ResolveError> {
+        if !reference.starts_with('#') {
+            return Err(ResolveError::External(String::from(reference)));
+        }
+        let mut current = self.root;
+        for segment in pointer_segments(&reference[1..]) {
+            current = match current {
+                Yaml::Hash(h) => {
+                    let by_string = h.get(&Yaml::String(segment.clone()));
+                    let by_integer = segment.parse::<i64>().ok().and_then(|i| h.get(&Yaml::Integer(i)));
+                    match by_string.or(by_integer) {
+                        Some(node) => node,
+                        None => return Err(ResolveError::NotFound(String::from(reference)))
+                    }
+                },
+                Yaml::Array(a) => {
+                    match segment.parse::<usize>().ok().and_then(|i| a.get(i)) {
+                        Some(node) => node,
+                        None => return Err(ResolveError::NotFound(String::from(reference)))
+                    }
+                },
+                _ => return Err(ResolveError::NotFound(String::from(reference)))
+            };
+        }
+        Ok(current)
+    }
+}
+
+//the model name if the reference points directly at a named schema
+pub fn schema_name(reference : &str) -> Option<String> {
+    let segments = pointer_segments(reference.trim_start_matches('#'));
+    match segments.as_slice() {
+        [components, schemas, name] if components == "components" && schemas == "schemas" => Some(name.clone()),
+        [definitions, name] if definitions == "definitions" => Some(name.clone()),
+        _ => None
+    }
+}
+
+//split a JSON pointer into its unescaped segments
+pub fn pointer_segments(pointer : &str) -> Vec<String> {
+    pointer.split('/')
+        .skip(1)
+        .map(|segment| percent_decode(segment).replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+//build a local reference from unescaped segments, the inverse of pointer_segments
+pub fn pointer(segments : &[&str]) -> String {
+    let mut pointer = String::from("#");
+    for segment in segments {
+        pointer.push('/');
+        pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+    }
+    pointer
+}
+
+fn percent_decode(segment : &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(hex, 16) {
+                decoded.push(value);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn load(yaml : &str) -> Yaml {
+        YamlLoader::load_from_str(yaml).unwrap().remove(0)
+    }
+
+    #[test]
+    pub fn test_resolve_chain() {
+        let doc = load("components:\n  parameters:\n    Id:\n      $ref: '#/components/parameters/RealId'\n    RealId:\n      name: id\n      in: path\n");
+        let resolver = Resolver::new(&doc);
+        let param = resolver.resolve(&doc["components"]["parameters"]["Id"]).unwrap();
+        assert_eq!(param["name"].as_str(), Some("id"));
+    }
+
+    #[test]
+    pub fn test_resolve_cycle() {
+        let doc = load("components:\n  schemas:\n    A:\n      $ref: '#/components/schemas/B'\n    B:\n      $ref: '#/components/schemas/A'\n");
+        let resolver = Resolver::new(&doc);
+        match resolver.resolve(&doc["components"]["schemas"]["A"]) {
+            Err(ResolveError::Cycle(_)) => {},
+            other => panic!("expected a cycle, got {:?}", other)
+        }
+    }
+
+    #[test]
+    pub fn test_escaped_pointer() {
+        let doc = load("paths:\n  /users/{id}:\n    get:\n      summary: user\n  200: ok\n");
+        let resolver = Resolver::new(&doc);
+        assert_eq!(resolver.lookup("#/paths/~1users~1%7Bid%7D/get/summary").unwrap().as_str(), Some("user"));
+        assert_eq!(resolver.lookup("#/paths/200").unwrap().as_str(), Some("ok"));
+        assert_eq!(schema_name("#/components/schemas/Order"), Some(String::from("Order")));
+        assert_eq!(schema_name("#/components/schemas/Order/properties/id"), None);
+        assert_eq!(pointer(&["paths", "/users/{id}", "get"]), "#/paths/~1users~1{id}/get");
+    }
+}
@@ -0,0 +1,156 @@
+//HTTP status codes with their reason phrases as registered at IANA,
+//see https://www.iana.org/assignments/http-status-codes
+static REGISTRY : [(&str, &str); 62] = [
+    ("100", "Continue"),
+    ("101", "Switching Protocols"),
+    ("102", "Processing"),
+    ("103", "Early Hints"),
+    ("200", "OK"),
+    ("201", "Created"),
+    ("202", "Accepted"),
+    ("203", "Non-Authoritative Information"),
+    ("204", "No Content"),
+    ("205", "Reset Content"),
+    ("206", "Partial Content"),
+    ("207", "Multi-Status"),
+    ("208", "Already Reported"),
+    ("226", "IM Used"),
+    ("300", "Multiple Choices"),
+    ("301", "Moved Permanently"),
+    ("302", "Found"),
+    ("303", "See Other"),
+    ("304", "Not Modified"),
+    ("305", "Use Proxy"),
+    ("307", "Temporary Redirect"),
+    ("308", "Permanent Redirect"),
+    ("400", "Bad Request"),
+    ("401", "Unauthorized"),
+    ("402", "Payment Required"),
+    ("403", "Forbidden"),
+    ("404", "Not Found"),
+    ("405", "Method Not Allowed"),
+    ("406", "Not Acceptable"),
+    ("407", "Proxy Authentication Required"),
+    ("408", "Request Timeout"),
+    ("409", "Conflict"),
+    ("410", "Gone"),
+    ("411", "Length Required"),
+    ("412", "Precondition Failed"),
+    ("413", "Content Too Large"),
+    ("414", "URI Too Long"),
+    ("415", "Unsupported Media Type"),
+    ("416", "Range Not Satisfiable"),
+    ("417", "Expectation Failed"),
+    ("421", "Misdirected Request"),
+    ("422", "Unprocessable Content"),
+    ("423", "Locked"),
+    ("424", "Failed Dependency"),
+    ("425", "Too Early"),
+    ("426", "Upgrade Required"),
+    ("428", "Precondition Required"),
+    ("429", "Too Many Requests"),
+    ("431", "Request Header Fields Too Large"),
+    ("451", "Unavailable For Legal Reasons"),
+    ("500", "Internal Server Error"),
+    ("501", "Not Implemented"),
+    ("502", "Bad Gateway"),
+    ("503", "Service Unavailable"),
+    ("504", "Gateway Timeout"),
+    ("505", "HTTP Version Not Supported"),
+    ("506", "Variant Also Negotiates"),
+    ("507", "Insufficient Storage"),
+    ("508", "Loop Detected"),
+    ("510", "Not Extended"),
+    ("511", "Network Authentication Required"),
+    ("default", "Any other status")
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusClass {
+    Informational,
+    Success,
+    Redirection,
+    ClientError,
+    ServerError,
+    //the default response of an operation, used for all codes which are not listed
+    Default
+}
+impl StatusClass {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusClass::Informational => "Informational",
+            StatusClass::Success => "Success",
+            StatusClass::Redirection => "Redirection",
+            StatusClass::ClientError => "Client Error",
+            StatusClass::ServerError => "Server Error",
+            StatusClass::Default => "Any other status"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpStatus {
+    pub code : String,
+    pub reason : &'static str,
+    pub class : Option<StatusClass>
+}
+impl HttpStatus {
+    //codes are either a single status, a range like 4XX or default
+    pub fn parse(code : &str) -> HttpStatus {
+        let code = code.trim();
+        let class = if code.eq_ignore_ascii_case("default") {
+            Some(StatusClass::Default)
+        } else if code.len() == 3 && code.is_ascii() {
+            match code.as_bytes()[0] {
+                b'1' => Some(StatusClass::Informational),
+                b'2' => Some(StatusClass::Success),
+                b'3' => Some(StatusClass::Redirection),
+                b'4' => Some(StatusClass::ClientError),
+                b'5' => Some(StatusClass::ServerError),
+                _ => None
+            }
+        } else {
+            None
+        };
+        let code = if class == Some(StatusClass::Default) {
+            String::from("default")
+        } else if class.is_some() && code[1..].eq_ignore_ascii_case("xx") {
+            code.to_uppercase()
+        } else {
+            String::from(code)
+        };
+        //unregistered codes and ranges are described by their class
+        let reason = REGISTRY.iter()
+            .find(|(registered, _)| *registered == code)
+            .map(|(_, reason)| *reason)
+            .or_else(|| class.map(|class| class.name()))
+            .unwrap_or("");
+        HttpStatus {
+            code,
+            reason,
+            class
+        }
+    }
+    //client and server errors, the default response usually describes the errors as well
+    pub fn is_error(&self) -> bool {
+        matches!(self.class, Some(StatusClass::ClientError) | Some(StatusClass::ServerError) | Some(StatusClass::Default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_status_codes() {
+        assert_eq!(HttpStatus::parse("404").reason, "Not Found");
+        assert!(HttpStatus::parse("404").is_error());
+        assert_eq!(HttpStatus::parse("201").reason, "Created");
+        assert!(!HttpStatus::parse("302").is_error());
+        assert_eq!(HttpStatus::parse("4xx"), HttpStatus { code : String::from("4XX"), reason : "Client Error", class : Some(StatusClass::ClientError) });
+        assert_eq!(HttpStatus::parse("299").reason, "Success");
+        assert_eq!(HttpStatus::parse("Default").code, "default");
+        assert!(HttpStatus::parse("default").is_error());
+        assert_eq!(HttpStatus::parse("abc").class, None);
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs;
+use std::io;
+use std::fmt;
+use std::error;
+use std::path::{Path, PathBuf};
+use regex::{Regex, Captures};
+use crate::cli::OutputFormat;
+
+//The default templates, compiled into the binary such that the tool works without a checkout of the repository
+pub static LATEX_TEMPLATES : [(&str, &str); 10] = [
+    ("documentation.tex.mustache", include_str!("../templates/latex/documentation.tex.mustache")),
+    ("titlepage.mustache", include_str!("../templates/latex/titlepage.mustache")),
+    ("request.mustache", include_str!("../templates/latex/request.mustache")),
+    ("schema.mustache", include_str!("../templates/latex/schema.mustache")),
+    ("param.mustache", include_str!("../templates/latex/param.mustache")),
+    ("content.mustache", include_str!("../templates/latex/content.mustache")),
+    ("encoding.mustache", include_str!("../templates/latex/encoding.mustache")),
+    ("schema_field.mustache", include_str!("../templates/latex/schema_field.mustache")),
+    ("server.mustache", include_str!("../templates/latex/server.mustache")),
+    ("security.mustache", include_str!("../templates/latex/security.mustache"))
+];
+pub static MARKDOWN_TEMPLATES : [(&str, &str); 8] = [
+    ("index.md.mustache", include_str!("../templates/markdown/index.md.mustache")),
+    ("tag.md.mustache", include_str!("../templates/markdown/tag.md.mustache")),
+    ("schema.md.mustache", include_str!("../templates/markdown/schema.md.mustache")),
+    ("operation.mustache", include_str!("../templates/markdown/operation.mustache")),
+    ("field.mustache", include_str!("../templates/markdown/field.mustache")),
+    ("server.mustache", include_str!("../templates/markdown/server.mustache")),
+    ("content.mustache", include_str!("../templates/markdown/content.mustache")),
+    ("encoding.mustache", include_str!("../templates/markdown/encoding.mustache"))
+];
+
+pub static HTML_TEMPLATES : [(&str, &str); 10] = [
+    ("documentation.html.mustache", include_str!("../templates/html/documentation.html.mustache")),
+    ("request.mustache", include_str!("../templates/html/request.mustache")),
+    ("schema.mustache", include_str!("../templates/html/schema.mustache")),
+    ("param.mustache", include_str!("../templates/html/param.mustache")),
+    ("schema_field.mustache", include_str!("../templates/html/schema_field.mustache")),
+    ("type.mustache", include_str!("../templates/html/type.mustache")),
+    ("server.mustache", include_str!("../templates/html/server.mustache")),
+    ("security.mustache", include_str!("../templates/html/security.mustache")),
+    ("content.mustache", include_str!("../templates/html/content.mustache")),
+    ("encoding.mustache", include_str!("../templates/html/encoding.mustache"))
+];
+
+pub fn defaults(format : OutputFormat) -> &'static [(&'static str, &'static str)] {
+    match format {
+        OutputFormat::Latex => &LATEX_TEMPLATES,
+        OutputFormat::Markdown => &MARKDOWN_TEMPLATES,
+        OutputFormat::Html => &HTML_TEMPLATES
+    }
+}
+
+static PARTIAL : &str = r"\{\{>\s*(?P<name>[\w./-]+)\s*\}\}";
+static MAX_PARTIAL_DEPTH : usize = 16;
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Io(PathBuf, io::Error),
+    Missing(String),
+    Recursion(String),
+    Compile(String, mustache::Error)
+}
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::Io(path, err) => write!(f, "Could not read template {}: {}", path.display(), err),
+            TemplateError::Missing(name) => write!(f, "There is no template named {}", name),
+            TemplateError::Recursion(name) => write!(f, "Partial {} includes itself", name),
+            TemplateError::Compile(name, err) => write!(f, "Could not compile template {}: {}", name, err)
+        }
+    }
+}
+impl error::Error for TemplateError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+//Templates are taken from the override directory if it contains them, otherwise the embedded default is used.
+//This allows to customise single partials without copying all of them.
+pub struct Templates {
+    defaults : &'static [(&'static str, &'static str)],
+    dir : Option<PathBuf>
+}
+impl Templates {
+    pub fn new(defaults : &'static [(&'static str, &'static str)], dir : Option<PathBuf>) -> Templates {
+        Templates {
+            defaults,
+            dir
+        }
+    }
+
+    pub fn source(&self, name : &str) -> Result<String, TemplateError> {
+        if let Some(ref dir) = self.dir {
+            let path = dir.join(name);
+            if path.exists() {
+                return fs::read_to_string(&path).map_err(|err| TemplateError::Io(path, err));
+            }
+        }
+        self.defaults.iter()
+            .find(|(default_name, _)| *default_name == name)
+            .map(|(_, content)| String::from(*content))
+            .ok_or_else(|| TemplateError::Missing(String::from(name)))
+    }
+
+    pub fn compile(&self, name : &str) -> Result<mustache::Template, TemplateError> {
+        let source = self.expand(&self.source(name)?, 0)?;
+        mustache::compile_str(&source).map_err(|err| TemplateError::Compile(String::from(name), err))
+    }
+
+    //mustache only loads partials from the file system, so they are inlined before compiling
+    fn expand(&self, source : &str, depth : usize) -> Result<String, TemplateError> {
+        let partial = Regex::new(PARTIAL).unwrap();
+        let mut error = None;
+        let expanded = partial.replace_all(source, |captures : &Captures| {
+            let name = format!("{}.mustache", &captures["name"]);
+            let result = if depth >= MAX_PARTIAL_DEPTH {
+                Err(TemplateError::Recursion(name))
+            } else {
+                self.source(&name).and_then(|partial_source| self.expand(&partial_source, depth + 1))
+            };
+            match result {
+                Ok(content) => content.trim_end_matches('\n').to_string(),
+                Err(err) => {
+                    error.get_or_insert(err);
+                    String::new()
+                }
+            }
+        });
+        match error {
+            Some(err) => Err(err),
+            None => Ok(expanded.into_owned())
+        }
+    }
+}
+
+//write the default templates into dir, existing files are only replaced when forced
+pub fn init(defaults : &[(&str, &str)], dir : &Path, force : bool) -> io::Result<Vec<PathBuf>> {
+    if !force {
+        if let Some(path) = defaults.iter().map(|(name, _)| dir.join(name)).find(|path| path.exists()) {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists, use --force to overwrite it", path.display())));
+        }
+    }
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for (name, content) in defaults.iter() {
+        let path = dir.join(name);
+        fs::write(&path, content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    pub fn test_partial_override() {
+        let dir = TempDir::new("templates");
+        dir.write("param.mustache", "{{field}}!\n");
+        let templates = Templates::new(&LATEX_TEMPLATES, Some(dir.path().to_path_buf()));
+        let source = templates.expand("{{#params}}{{> param}}{{/params}}", 0).unwrap();
+        assert_eq!(source, "{{#params}}{{field}}!{{/params}}");
+        assert!(templates.expand("{{>schema}}", 0).unwrap().contains("\\label{sec:{{label}}}"));
+        dir.write("loop.mustache", "{{> loop}}");
+        match templates.expand("{{> loop}}", 0) {
+            Err(TemplateError::Recursion(_)) => {},
+            other => panic!("expected a recursion error, got {:?}", other)
+        }
+    }
+}
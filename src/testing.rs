@@ -0,0 +1,37 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+//A directory for the files of a test, it is removed when the test ends, also when an assertion failed
+pub struct TempDir {
+    path : PathBuf
+}
+impl TempDir {
+    pub fn new(name : &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("rusty-swagger-{}-{}", name, std::process::id()));
+        //left over by a test which was killed
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir {
+            path
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    //write a file relative to the directory, missing parent directories are created
+    pub fn write(&self, name : &str, content : &str) -> PathBuf {
+        let path = self.path.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, content).unwrap();
+        path
+    }
+}
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
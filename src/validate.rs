@@ -0,0 +1,96 @@
+use yaml_rust::Yaml;
+use crate::resolver::Resolver;
+
+//Checks for problems in a specification which would lead to missing or wrong parts in the documentation
+pub fn validate(document_root : &Yaml) -> Vec<String> {
+    let mut problems = Vec::new();
+    if document_root["openapi"].is_badvalue() && document_root["swagger"].is_badvalue() {
+        problems.push(String::from("the document declares neither an openapi nor a swagger version"));
+    }
+    if document_root["info"]["title"].as_str().is_none() {
+        problems.push(String::from("info.title is missing"));
+    }
+    if let Yaml::Hash(_) = document_root["paths"] {
+    } else {
+        problems.push(String::from("paths is missing"));
+    }
+    let resolver = Resolver::new(document_root);
+    check_references(&resolver, document_root, &mut String::from("#"), &mut problems);
+    check_security(document_root, &document_root["security"], "#/security", &mut problems);
+    if let Yaml::Hash(ref paths) = document_root["paths"] {
+        for (path, path_item) in paths {
+            if let Yaml::Hash(ref operations) = path_item {
+                for (method, operation) in operations {
+                    let location = format!("#/paths/{}/{}/security", path.as_str().unwrap_or("?").replace('~', "~0").replace('/', "~1"), method.as_str().unwrap_or("?"));
+                    check_security(document_root, &operation["security"], &location, &mut problems);
+                }
+            }
+        }
+    }
+    problems
+}
+
+//security requirements have to name a scheme declared in components.securitySchemes
+fn check_security(document_root : &Yaml, security : &Yaml, location : &str, problems : &mut Vec<String>) {
+    if let Yaml::Array(ref requirements) = security {
+        for (i, requirement) in requirements.iter().enumerate() {
+            if let Yaml::Hash(ref requirement) = requirement {
+                for name in requirement.keys() {
+                    if document_root["components"]["securitySchemes"][name.as_str().unwrap_or("")].is_badvalue() {
+                        problems.push(format!("{}/{}: the security scheme {} is not declared", location, i, name.as_str().unwrap_or("?")));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_references<'a>(resolver : &Resolver<'a>, node : &'a Yaml, location : &mut String, problems : &mut Vec<String>) {
+    match node {
+        Yaml::Hash(h) => {
+            if let Yaml::String(_) = node["$ref"] {
+                if let Err(err) = resolver.resolve(node) {
+                    problems.push(format!("{}: {}", location, err));
+                }
+            }
+            for (k, v) in h {
+                let key = match k {
+                    Yaml::Integer(i) => i.to_string(),
+                    _ => String::from(k.as_str().unwrap_or("?"))
+                };
+                let length = location.len();
+                location.push('/');
+                location.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                check_references(resolver, v, location, problems);
+                location.truncate(length);
+            }
+        },
+        Yaml::Array(a) => {
+            for (i, v) in a.iter().enumerate() {
+                let length = location.len();
+                location.push_str(&format!("/{}", i));
+                check_references(resolver, v, location, problems);
+                location.truncate(length);
+            }
+        },
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    pub fn test_broken_references() {
+        let doc = YamlLoader::load_from_str("openapi: 3.0.0\ninfo: {title: t}\npaths:\n  /a:\n    get:\n      parameters:\n        - $ref: '#/components/parameters/Missing'\n").unwrap().remove(0);
+        assert_eq!(validate(&doc), vec![String::from("#/paths/~1a/get/parameters/0: Could not resolve reference #/components/parameters/Missing")]);
+    }
+
+    #[test]
+    pub fn test_undeclared_security_scheme() {
+        let doc = YamlLoader::load_from_str("openapi: 3.0.0\ninfo: {title: t}\nsecurity:\n  - api_key: []\npaths:\n  /a:\n    get:\n      security:\n        - {}\n        - oauth: [read]\ncomponents:\n  securitySchemes:\n    api_key: {type: apiKey, name: key, in: header}\n").unwrap().remove(0);
+        assert_eq!(validate(&doc), vec![String::from("#/paths/~1a/get/security/1: the security scheme oauth is not declared")]);
+    }
+}